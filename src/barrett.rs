@@ -0,0 +1,243 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Barrett reduction context for any nonzero modulus m of k words, using
+// mu = floor(b^2k / m) with b = 2^32, held at the next depth up.
+
+use crate::limbs;
+use crate::short;
+use crate::{ModContext, Uintz, Uz};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Barrett<T: Uintz>
+where
+    Uz<T>: Uintz,
+{
+    m: Uz<T>,
+    k: usize,
+    mu: Uz<Uz<T>>,
+}
+
+impl<T: Uintz> Barrett<T>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    pub fn new(modulus: Uz<T>) -> Option<Self> {
+        let k = modulus.bits().div_ceil(32) as usize;
+        if k == 0 {
+            return None;
+        }
+        // b^2k may not fit the wider type; divide b^2k - 1 and fix up.
+        let m = modulus.augment();
+        let e = m.zero().max_value().shr(32 * (m.limbs() - 2 * k) as u32);
+        let (mut mu, r) = e.divr(m);
+        if r.addc32(1, false).0 == m {
+            mu = mu.addc32(1, false).0;
+        }
+        // Only m = b^(k - 1) gives mu = b^(k + 1), a word too wide; one
+        // less costs reduce at most one more subtraction.
+        if mu.bits() as usize > 32 * (k + 1) {
+            mu = mu.subb32(1, false).0;
+        }
+        Some(Barrett { m: modulus, k, mu })
+    }
+
+    // HAC 14.42: the quotient estimate q3 is the high half of q1 mu, and
+    // r is only needed modulo b^(k + 1), so both products are short ones
+    // at k + 1 words.
+    pub fn reduce(&self, x: Uz<Uz<T>>) -> Uz<T> {
+        let k = self.k;
+        if x.bits() as usize > 64 * k {
+            return x.divr(self.m.augment()).1.lo;
+        }
+        let n = self.m.limbs();
+        let w = n.max(k + 1);
+        let mut buf = vec![0; 4 * n + 2 * w];
+        let (xl, rest) = buf.split_at_mut(2 * n);
+        let (mu, rest) = rest.split_at_mut(2 * n);
+        let (m, r) = rest.split_at_mut(w);
+        x.write_limbs(xl);
+        self.mu.write_limbs(mu);
+        self.m.write_limbs(m);
+        let q3 = short::mul_hi(&xl[k - 1..2 * k], &mu[..k + 1]);
+        r[..k + 1].copy_from_slice(&xl[..k + 1]);
+        limbs::sub_assign(&mut r[..k + 1], &short::mul_lo(&q3, &m[..k + 1]));
+        while limbs::cmp(&r[..k + 1], m).is_ge() {
+            limbs::sub_assign(&mut r[..k + 1], &m[..k + 1]);
+        }
+        self.m.read_limbs(&r[..n])
+    }
+
+    pub fn mul_mod(&self, a: Uz<T>, b: Uz<T>) -> Uz<T> {
+        let (lo, hi) = a.mulc(b, a.zero());
+        self.reduce(Uz { hi, lo })
+    }
+
+    pub fn pow_mod(&self, a: Uz<T>, exp: Uz<T>) -> Uz<T> {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let a = self.reduce(a.augment());
+        let mut r = self.reduce(a.zero().addc32(1, false).0.augment());
+        for i in (0..exp.bits()).rev() {
+            r = self.mul_mod(r, r);
+            if e[i as usize / 32] >> (i % 32) & 1 != 0 {
+                r = self.mul_mod(r, a);
+            }
+        }
+        r
+    }
+}
+
+impl<T: Uintz> ModContext<T> for Barrett<T>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    fn decode(&self, x: Uz<T>) -> Uz<T> {
+        x
+    }
+
+    fn encode(&self, x: Uz<T>) -> Uz<T> {
+        self.reduce(x.augment())
+    }
+
+    fn modulus(&self) -> Uz<T> {
+        self.m
+    }
+
+    fn mul(&self, a: Uz<T>, b: Uz<T>) -> Uz<T> {
+        self.mul_mod(a, b)
+    }
+
+    fn one(&self) -> Uz<T> {
+        self.encode(self.m.zero().addc32(1, false).0)
+    }
+
+    fn pow(&self, a: Uz<T>, exp: Uz<T>) -> Uz<T> {
+        self.pow_mod(a, exp)
+    }
+
+    fn square(&self, a: Uz<T>) -> Uz<T> {
+        self.mul_mod(a, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Barrett;
+    use crate::montgomery::Montgomery;
+    use crate::{from_u32, ModContext, Uintz, Uz, Uz32};
+
+    fn uz64(v: u64) -> Uz<Uz32> {
+        from_u32(0)
+            .augment()
+            .read_limbs(&[v as u32, (v >> 32) as u32])
+    }
+
+    fn uz256(v: &[u32]) -> Uz<Uz<Uz<Uz32>>> {
+        from_u32(0).augment().augment().augment().read_limbs(v)
+    }
+
+    #[test]
+    fn new0() {
+        assert!(Barrett::new(uz64(0)).is_none());
+        assert!(Barrett::new(uz64(1)).is_some());
+        assert!(Barrett::new(uz64(u64::MAX)).is_some());
+    }
+
+    #[test]
+    fn reduce0() {
+        let b = Barrett::new(uz64(1_000_000_007)).unwrap();
+        let x = uz64(u64::MAX).augment();
+        assert_eq!(b.reduce(x), uz64(u64::MAX % 1_000_000_007));
+        let x = x.shl(64).addc(x, false).0;
+        assert_eq!(b.reduce(x), x.divr(uz64(1_000_000_007).augment()).1.lo);
+        assert_eq!(Barrett::new(uz64(1)).unwrap().reduce(x), uz64(0));
+    }
+
+    #[test]
+    fn mul_mod0() {
+        for &n in &[1u64, 2, 3, 10, 1 << 32, 0xffff_ffff, 1 << 63, u64::MAX] {
+            let n = uz64(n);
+            let b = Barrett::new(n).unwrap();
+            let mut x = uz64(0x0123_4567_89ab_cdef);
+            let mut y = uz64(0xfedc_ba98_7654_3210);
+            for _ in 0..20 {
+                assert_eq!(b.mul_mod(x, y), x.mul_mod(y, n));
+                x = x.mul_lo(y).addc32(0x9e37_79b9, false).0;
+                y = y.square().addc32(7, false).0;
+            }
+        }
+    }
+
+    #[test]
+    fn mul_mod1() {
+        let n = uz256(&[0, 0, 0, 0, 0x1234_5678, 0, 0, 0x4000_0000]);
+        for &n in &[n, n.shr(100), n.max_value()] {
+            let b = Barrett::new(n).unwrap();
+            let mut x = uz256(&[1, 2, 3, 4, 5, 6, 7, 0xffff_ffff]);
+            let mut y = uz256(&[0xdead_beef, 0, 0, 0, 0, 0, 0, 0x8000_0000]);
+            for _ in 0..20 {
+                assert_eq!(b.mul_mod(x, y), x.mul_mod(y, n));
+                x = x.mul_lo(y).addc32(0x9e37_79b9, false).0;
+                y = y.square().addc32(7, false).0;
+            }
+        }
+    }
+
+    #[test]
+    fn pow_mod0() {
+        let n = uz256(&[0, 0, 0, 0, 0, 0, 0, 0x8000_0000])
+            .addc32(12, false)
+            .0;
+        let b = Barrett::new(n).unwrap();
+        let a = uz256(&[0x1234_5678, 0x9abc_def0, 0, 0, 5, 0, 0, 0]);
+        for &e in &[uz256(&[0; 8]), uz256(&[65537, 0, 0, 0, 0, 0, 0, 0]), n] {
+            assert_eq!(b.pow_mod(a, e), a.pow_mod(e, n));
+        }
+    }
+
+    fn square_chain<T: Uintz, C: ModContext<T>>(c: &C, a: Uz<T>, n: u32) -> Uz<T>
+    where
+        Uz<T>: Uintz,
+    {
+        let mut x = c.encode(a);
+        let y = c.mul(x, c.one());
+        for _ in 0..n {
+            x = c.square(x);
+        }
+        c.decode(c.mul(x, y))
+    }
+
+    #[test]
+    fn mod_context0() {
+        let n = uz256(&[0xffff_ffed, 0xffff_ffff, 0, 0, 0, 0, 0, 0x7fff_ffff]);
+        let a = uz256(&[3, 0, 0, 0, 0, 0, 9, 0]);
+        let b = Barrett::new(n).unwrap();
+        let m = Montgomery::new(n).unwrap();
+        assert_eq!(square_chain(&b, a, 50), square_chain(&m, a, 50));
+        assert_eq!(
+            b.decode(b.pow(b.encode(a), n)),
+            m.decode(m.pow(m.encode(a), n))
+        );
+        assert_eq!(b.modulus(), m.modulus());
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Chinese remainder reconstruction over pairwise coprime moduli,
+// folding in one congruence at a time (Garner's scheme).
+
+use crate::{Uintz, Uz};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    Empty,
+    NotCoprime,
+    Overflow,
+    ZeroModulus,
+}
+
+// Returns the unique x below the product M of the moduli such that
+// x = r (mod m) for every (r, m) pair, along with M.
+pub fn crt<T: Uintz>(pairs: &[(T, T)]) -> Result<(T, T), CrtError> {
+    if pairs.iter().any(|&(_, m)| m == m.zero()) {
+        return Err(CrtError::ZeroModulus);
+    }
+    let (r, m) = *pairs.first().ok_or(CrtError::Empty)?;
+    let (mut x, mut prod) = (r.divr(m).1, m);
+    for &(r, m) in pairs[1..].iter() {
+        let inv = prod.divr(m).1.inv_mod(m).ok_or(CrtError::NotCoprime)?;
+        let (next, hi) = prod.mulc(m, m.zero());
+        if hi != m.zero() {
+            return Err(CrtError::Overflow);
+        }
+        // x + prod * ((r - x) / prod mod m) is below prod * m.
+        let t = r.sub_mod(x.divr(m).1, m).mul_mod(inv, m);
+        x = x.addc(prod.mul_lo(t), false).0;
+        prod = next;
+    }
+    Ok((x, prod))
+}
+
+// As crt, with the solution and the modulus product at the next depth up.
+pub fn crt_wide<T: Uintz>(pairs: &[(T, T)]) -> Result<(Uz<T>, Uz<T>), CrtError>
+where
+    Uz<T>: Uintz,
+{
+    let wide: Vec<_> = pairs
+        .iter()
+        .map(|&(r, m)| (r.augment(), m.augment()))
+        .collect();
+    crt(&wide)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crt, crt_wide, CrtError};
+    use crate::{from_u32, Uintz, Uz, Uz32};
+
+    fn uz64(v: u64) -> Uz<Uz32> {
+        from_u32(0)
+            .augment()
+            .read_limbs(&[v as u32, (v >> 32) as u32])
+    }
+
+    #[test]
+    fn crt0() {
+        let pairs = [(2, 3), (3, 5), (2, 7)];
+        let pairs: Vec<_> = pairs
+            .iter()
+            .map(|&(r, m)| (from_u32(r), from_u32(m)))
+            .collect();
+        assert_eq!(crt(&pairs), Ok((from_u32(23), from_u32(105))));
+        assert_eq!(crt(&pairs[..1]), Ok((from_u32(2), from_u32(3))));
+        let pairs = [(from_u32(10), from_u32(7)), (from_u32(0), from_u32(1))];
+        assert_eq!(crt(&pairs), Ok((from_u32(3), from_u32(7))));
+        let pairs = [(from_u32(1), from_u32(6)), (from_u32(3), from_u32(4))];
+        assert_eq!(crt(&pairs), Err(CrtError::NotCoprime));
+        let pairs = [
+            (from_u32(1), from_u32(65537)),
+            (from_u32(3), from_u32(65539)),
+        ];
+        assert_eq!(crt(&pairs), Err(CrtError::Overflow));
+        assert_eq!(crt::<Uz32>(&[]), Err(CrtError::Empty));
+        let pairs = [(from_u32(1), from_u32(3)), (from_u32(0), from_u32(0))];
+        assert_eq!(crt(&pairs), Err(CrtError::ZeroModulus));
+    }
+
+    #[test]
+    fn crt_wide0() {
+        let (a, b) = (65537u64, 65539u64);
+        let x = 1234567890123u64 % (a * b);
+        let pairs = [
+            (from_u32((x % a) as u32), from_u32(a as u32)),
+            (from_u32((x % b) as u32), from_u32(b as u32)),
+        ];
+        assert_eq!(crt_wide(&pairs), Ok((uz64(x), uz64(a * b))));
+    }
+
+    #[test]
+    fn crt_wide1() {
+        // The NTT primes recombine a convolution coefficient of 90 bits.
+        let primes = [3221225473u64, 2013265921, 469762049];
+        let x = uz64(0x0123_4567_89ab_cdef)
+            .augment()
+            .shl(26)
+            .addc32(77, false)
+            .0;
+        let pairs: Vec<_> = primes
+            .iter()
+            .map(|&p| (uz64(x.divr32(p as u32).1 as u64), uz64(p)))
+            .collect();
+        let m = uz64(primes[0] * primes[1])
+            .augment()
+            .mulc32(primes[2] as u32, x.zero())
+            .0;
+        assert_eq!(crt_wide(&pairs), Ok((x, m)));
+        assert_eq!(crt(&pairs), Err(CrtError::Overflow));
+    }
+}
@@ -0,0 +1,440 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Unsigned decimal fixed point: a Uz mantissa counting units of
+// 10^-SCALE. Addition and subtraction are exact; products and quotients
+// are computed on a double-width intermediate and rounded once.
+
+use crate::divisor::Divisor32;
+use crate::{Uintz, Uz};
+use std::fmt;
+use std::ops::{Add, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    HalfEven,
+    HalfUp,
+    Floor,
+    Ceil,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    Empty,
+    InvalidDigit,
+    TooManyDecimals,
+    Overflow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Decimal<T: Uintz, const SCALE: u32> {
+    m: Uz<T>,
+}
+
+fn ten_pow<W: Uintz>(proto: W, n: u32) -> Option<W> {
+    proto.zero().addc32(10, false).0.checked_pow(n)
+}
+
+// q + 1 if the discarded remainder r of a division by d calls for it.
+fn round<W: Uintz>(q: W, r: W, d: W, mode: Rounding) -> Option<W> {
+    let zero = q.zero();
+    let half = d.subb(r, false).0;
+    let up = match mode {
+        Rounding::Floor => false,
+        Rounding::Ceil => r != zero,
+        Rounding::HalfUp => r >= half,
+        Rounding::HalfEven => r > half || (r == half && q.lo32() & 1 == 1),
+    };
+    if !up {
+        return Some(q);
+    }
+    match q.addc32(1, false) {
+        (q, false) => Some(q),
+        _ => None,
+    }
+}
+
+fn narrow<T: Uintz>(w: Uz<Uz<T>>) -> Option<Uz<T>>
+where
+    Uz<T>: Uintz,
+{
+    if w.hi == w.hi.zero() {
+        Some(w.lo)
+    } else {
+        None
+    }
+}
+
+fn widen<T: Uintz>(x: Uz<T>) -> Uz<Uz<T>>
+where
+    Uz<T>: Uintz,
+{
+    Uz {
+        hi: x.zero(),
+        lo: x,
+    }
+}
+
+impl<T: Uintz, const SCALE: u32> Decimal<T, SCALE>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    // Panics unless 10^SCALE fits the mantissa.
+    fn unit(proto: Uz<T>) -> Uz<T> {
+        ten_pow(proto, SCALE).expect("decimal scale too large for the mantissa")
+    }
+
+    pub fn from_mantissa(m: Uz<T>) -> Self {
+        Decimal { m }
+    }
+
+    pub fn from_int(n: Uz<T>) -> Option<Self> {
+        match n.mulc(Self::unit(n), n.zero()) {
+            (m, hi) if hi == n.zero() => Some(Decimal { m }),
+            _ => None,
+        }
+    }
+
+    pub fn zero(proto: Uz<T>) -> Self {
+        Decimal { m: proto.zero() }
+    }
+
+    pub fn mantissa(self) -> Uz<T> {
+        self.m
+    }
+
+    pub fn scale(self) -> u32 {
+        SCALE
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        match self.m.addc(other.m, false) {
+            (m, false) => Some(Decimal { m }),
+            _ => None,
+        }
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.m.subb(other.m, false) {
+            (m, false) => Some(Decimal { m }),
+            _ => None,
+        }
+    }
+
+    // None on overflow.
+    pub fn mul(self, other: Self, mode: Rounding) -> Option<Self> {
+        let (lo, hi) = self.m.mulc(other.m, self.m.zero());
+        let d = widen(Self::unit(self.m));
+        let (q, r) = Uz { hi, lo }.divr(d);
+        narrow(round(q, r, d, mode)?).map(|m| Decimal { m })
+    }
+
+    // None on overflow; panics if other is zero.
+    pub fn div(self, other: Self, mode: Rounding) -> Option<Self> {
+        if other.m == other.m.zero() {
+            panic!("attempt to divide by zero");
+        }
+        let (lo, hi) = self.m.mulc(Self::unit(self.m), self.m.zero());
+        let d = widen(other.m);
+        let (q, r) = Uz { hi, lo }.divr(d);
+        narrow(round(q, r, d, mode)?).map(|m| Decimal { m })
+    }
+
+    // The same amount at another scale, rounded when decimals are
+    // dropped; None on overflow.
+    pub fn rescale<const S: u32>(self, mode: Rounding) -> Option<Decimal<T, S>> {
+        let m = if S >= SCALE {
+            match self.m.mulc(ten_pow(self.m, S - SCALE)?, self.m.zero()) {
+                (m, hi) if hi == self.m.zero() => m,
+                _ => return None,
+            }
+        } else {
+            match ten_pow(self.m, SCALE - S) {
+                Some(d) => {
+                    let (q, r) = self.m.divr(d);
+                    round(q, r, d, mode)?
+                }
+                // A divisor beyond the mantissa range is over twice any
+                // mantissa, so only Ceil rounds up.
+                None if mode == Rounding::Ceil && self.m != self.m.zero() => {
+                    self.m.zero().addc32(1, false).0
+                }
+                None => self.m.zero(),
+            }
+        };
+        Some(Decimal { m })
+    }
+
+    // Digits with an optional point and up to SCALE decimals, such as
+    // "12", "12.5" or ".05". The prototype gives the mantissa width.
+    pub fn parse(s: &str, proto: Uz<T>) -> Result<Self, ParseDecimalError> {
+        let (int, frac) = match s.find('.') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, ""),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return Err(ParseDecimalError::Empty);
+        }
+        if frac.len() > SCALE as usize {
+            return Err(ParseDecimalError::TooManyDecimals);
+        }
+        let pad = SCALE as usize - frac.len();
+        let mut m = proto.zero();
+        for c in int.chars().chain(frac.chars()) {
+            let d = c.to_digit(10).ok_or(ParseDecimalError::InvalidDigit)?;
+            let (v, hi) = m.mulc32(10, proto.zero());
+            let (v, carry) = v.addc32(d, false);
+            if hi != proto.zero() || carry {
+                return Err(ParseDecimalError::Overflow);
+            }
+            m = v;
+        }
+        for _ in 0..pad {
+            let (v, hi) = m.mulc32(10, proto.zero());
+            if hi != proto.zero() {
+                return Err(ParseDecimalError::Overflow);
+            }
+            m = v;
+        }
+        Ok(Decimal { m })
+    }
+}
+
+impl<T: Uintz, const SCALE: u32> Add for Decimal<T, SCALE>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl<T: Uintz, const SCALE: u32> Sub for Decimal<T, SCALE>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+// Decimal digits of x, nine at a time from the least significant end.
+pub(crate) fn to_decimal<W: Uintz>(x: W) -> String {
+    let billion = Divisor32::new(1_000_000_000);
+    let mut chunks = Vec::new();
+    let mut x = x;
+    loop {
+        let (q, r) = x.divr32_pre(&billion, 0);
+        chunks.push(r);
+        x = q;
+        if x == x.zero() {
+            break;
+        }
+    }
+    let mut s = chunks.pop().unwrap().to_string();
+    for c in chunks.iter().rev() {
+        s.push_str(&format!("{:09}", c));
+    }
+    s
+}
+
+impl<T: Uintz, const SCALE: u32> fmt::Display for Decimal<T, SCALE>
+where
+    Uz<T>: Uintz,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = to_decimal(self.m);
+        let scale = SCALE as usize;
+        if scale > 0 {
+            if s.len() <= scale {
+                s = "0".repeat(scale + 1 - s.len()) + &s;
+            }
+            s.insert(s.len() - scale, '.');
+        }
+        f.pad_integral(true, "", &s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decimal, ParseDecimalError, Rounding};
+    use crate::{from_u32, Uintz, Uz, Uz32};
+
+    type U128 = Uz<Uz<Uz32>>;
+    type U256 = Uz<U128>;
+    type Money = Decimal<Uz<Uz32>, 2>;
+    type Wide = Decimal<U128, 18>;
+
+    fn u128_proto() -> U128 {
+        from_u32(0).augment().augment()
+    }
+
+    fn u256_proto() -> U256 {
+        u128_proto().augment()
+    }
+
+    fn money(s: &str) -> Money {
+        Money::parse(s, u128_proto()).unwrap()
+    }
+
+    fn wide(s: &str) -> Wide {
+        Wide::parse(s, u256_proto()).unwrap()
+    }
+
+    #[test]
+    fn parse0() {
+        assert_eq!(money("12.5").to_string(), "12.50");
+        assert_eq!(money(".05").to_string(), "0.05");
+        assert_eq!(money("7").to_string(), "7.00");
+        assert_eq!(money("0").to_string(), "0.00");
+        assert_eq!(money("007.").to_string(), "7.00");
+        assert_eq!(money("12.5").mantissa(), u128_proto().addc32(1250, false).0);
+        let max = "3402823669209384634633746074317682114.55";
+        assert_eq!(money(max).to_string(), max);
+        let p = |s| Money::parse(s, u128_proto());
+        assert_eq!(p(""), Err(ParseDecimalError::Empty));
+        assert_eq!(p("."), Err(ParseDecimalError::Empty));
+        assert_eq!(p("1.234"), Err(ParseDecimalError::TooManyDecimals));
+        assert_eq!(p("1,5"), Err(ParseDecimalError::InvalidDigit));
+        assert_eq!(p("-1"), Err(ParseDecimalError::InvalidDigit));
+        assert_eq!(
+            p("3402823669209384634633746074317682114.56"),
+            Err(ParseDecimalError::Overflow)
+        );
+        let d = Decimal::<Uz<Uz32>, 0>::parse("42", u128_proto()).unwrap();
+        assert_eq!(d.to_string(), "42");
+    }
+
+    #[test]
+    fn fmt0() {
+        assert_eq!(format!("{:>8}", money("12.5")), "   12.50");
+        assert_eq!(format!("{:<8}|", money("12.5")), "12.50   |");
+        assert_eq!(format!("{:*^9}", money("12.5")), "**12.50**");
+        assert_eq!(format!("{:08}", money("12.5")), "00012.50");
+        assert_eq!(format!("{:+}", money("0.05")), "+0.05");
+        assert_eq!(format!("{:2}", money("12.5")), "12.50");
+    }
+
+    #[test]
+    fn add0() {
+        assert_eq!((money("0.10") + money("0.20")).to_string(), "0.30");
+        assert_eq!((money("100") - money("0.01")).to_string(), "99.99");
+        assert_eq!(money("0.01").checked_sub(money("0.02")), None);
+        let max = Money::from_mantissa(u128_proto().max_value());
+        assert_eq!(max.checked_add(money("0.01")), None);
+        assert_eq!(
+            Money::from_int(u128_proto().addc32(3, false).0),
+            Some(money("3"))
+        );
+        assert_eq!(Money::from_int(u128_proto().max_value()), None);
+    }
+
+    #[test]
+    fn mul0() {
+        let (a, b) = (money("1.25"), money("0.10"));
+        // 0.125 sits exactly halfway.
+        assert_eq!(a.mul(b, Rounding::HalfEven).unwrap(), money("0.12"));
+        assert_eq!(a.mul(b, Rounding::HalfUp).unwrap(), money("0.13"));
+        assert_eq!(a.mul(b, Rounding::Floor).unwrap(), money("0.12"));
+        assert_eq!(a.mul(b, Rounding::Ceil).unwrap(), money("0.13"));
+        let c = money("1.35");
+        assert_eq!(c.mul(b, Rounding::HalfEven).unwrap(), money("0.14"));
+        assert_eq!(
+            money("19.99").mul(money("3"), Rounding::Floor),
+            Some(money("59.97"))
+        );
+        let big = money("3402823669209384634633746074317682114.55");
+        assert_eq!(big.mul(money("2"), Rounding::Floor), None);
+        assert_eq!(big.mul(money("1"), Rounding::Floor), Some(big));
+    }
+
+    #[test]
+    fn div0() {
+        let (a, b) = (money("10"), money("3"));
+        assert_eq!(a.div(b, Rounding::HalfEven).unwrap(), money("3.33"));
+        assert_eq!(a.div(b, Rounding::Ceil).unwrap(), money("3.34"));
+        assert_eq!(money("2").div(b, Rounding::HalfUp).unwrap(), money("0.67"));
+        assert_eq!(money("2").div(b, Rounding::Floor).unwrap(), money("0.66"));
+        assert_eq!(
+            money("0.01")
+                .div(money("0.08"), Rounding::HalfEven)
+                .unwrap(),
+            money("0.12")
+        );
+        assert_eq!(
+            money("0.03")
+                .div(money("0.08"), Rounding::HalfEven)
+                .unwrap(),
+            money("0.38")
+        );
+        let big = money("3402823669209384634633746074317682114.55");
+        assert_eq!(big.div(money("0.5"), Rounding::Floor), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn div1() {
+        money("1").div(money("0"), Rounding::Floor);
+    }
+
+    #[test]
+    fn wide0() {
+        let a = wide("123456789012345678901234567890.123456789012345678");
+        assert_eq!(
+            a.to_string(),
+            "123456789012345678901234567890.123456789012345678"
+        );
+        let b = a.mul(wide("1000000"), Rounding::HalfEven).unwrap();
+        assert_eq!(
+            b.to_string(),
+            "123456789012345678901234567890123456.789012345678000000"
+        );
+        assert_eq!(b.div(wide("1000000"), Rounding::HalfEven), Some(a));
+        let third = wide("1").div(wide("3"), Rounding::HalfEven).unwrap();
+        assert_eq!(third.to_string(), "0.333333333333333333");
+        assert_eq!((third + third + third).to_string(), "0.999999999999999999");
+    }
+
+    #[test]
+    fn rescale0() {
+        let a = Decimal::<Uz<Uz32>, 3>::parse("2.345", u128_proto()).unwrap();
+        assert_eq!(a.rescale::<2>(Rounding::HalfEven), Some(money("2.34")));
+        assert_eq!(a.rescale::<2>(Rounding::HalfUp), Some(money("2.35")));
+        assert_eq!(a.rescale::<2>(Rounding::Floor), Some(money("2.34")));
+        assert_eq!(a.rescale::<0>(Rounding::Ceil).unwrap().to_string(), "3");
+        let b = money("2.34").rescale::<5>(Rounding::Floor).unwrap();
+        assert_eq!(b.to_string(), "2.34000");
+        assert_eq!(b.rescale::<2>(Rounding::Floor), Some(money("2.34")));
+        // 10^40 does not fit 128 bits: everything rounds to 0 or 1 unit.
+        let c = Decimal::<Uz<Uz32>, 40>::from_mantissa(u128_proto().addc32(5, false).0);
+        assert_eq!(c.rescale::<0>(Rounding::Ceil).unwrap().to_string(), "1");
+        assert_eq!(c.rescale::<0>(Rounding::HalfUp).unwrap().to_string(), "0");
+        assert_eq!(money("1").rescale::<40>(Rounding::Floor), None);
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Division by an invariant 32-bit divisor through a precomputed
+// reciprocal of the normalized divisor (Moller & Granlund, "Improved
+// division by invariant integers"), trading the hardware division in
+// divr32 for two multiplications per limb.
+
+use crate::{Uintz, Uz, Uz32};
+use std::ops::{Div, Rem};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divisor32 {
+    d: u32,
+    shift: u32,
+    // floor((2^64 - 1) / (d << shift)) - 2^32
+    v: u32,
+}
+
+impl Divisor32 {
+    pub fn new(divisor: u32) -> Self {
+        if divisor == 0 {
+            panic!("attempt to divide by zero");
+        }
+        let shift = divisor.leading_zeros();
+        let n = divisor << shift;
+        Divisor32 {
+            d: divisor,
+            shift,
+            v: (u64::MAX / n as u64 - (1 << 32)) as u32,
+        }
+    }
+
+    pub fn divisor(&self) -> u32 {
+        self.d
+    }
+
+    pub fn div_rem<T: Uintz>(&self, x: T) -> (T, u32) {
+        x.divr32_pre(self, 0)
+    }
+
+    // Divides remainder:x by the divisor, where remainder < divisor.
+    pub fn step(&self, remainder: u32, x: u32) -> (u32, u32) {
+        let s = self.shift;
+        let n = self.d << s;
+        let u1 = if s == 0 {
+            remainder
+        } else {
+            remainder << s | x >> (32 - s)
+        };
+        let u0 = x << s;
+        let p = self.v as u64 * u1 as u64 + ((u1 as u64) << 32 | u0 as u64);
+        let mut q = ((p >> 32) as u32).wrapping_add(1);
+        let mut r = u0.wrapping_sub(q.wrapping_mul(n));
+        if r > p as u32 {
+            q = q.wrapping_sub(1);
+            r = r.wrapping_add(n);
+        }
+        if r >= n {
+            q += 1;
+            r -= n;
+        }
+        (q, r >> s)
+    }
+}
+
+impl Div<Divisor32> for Uz32 {
+    type Output = Uz32;
+
+    fn div(self, divisor: Divisor32) -> Uz32 {
+        divisor.div_rem(self).0
+    }
+}
+
+impl Rem<Divisor32> for Uz32 {
+    type Output = u32;
+
+    fn rem(self, divisor: Divisor32) -> u32 {
+        divisor.div_rem(self).1
+    }
+}
+
+impl<T: Uintz> Div<Divisor32> for Uz<T>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Uz<T>;
+
+    fn div(self, divisor: Divisor32) -> Uz<T> {
+        divisor.div_rem(self).0
+    }
+}
+
+impl<T: Uintz> Rem<Divisor32> for Uz<T>
+where
+    Uz<T>: Uintz,
+{
+    type Output = u32;
+
+    fn rem(self, divisor: Divisor32) -> u32 {
+        divisor.div_rem(self).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Divisor32;
+    use crate::{from_u32, Uintz};
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn new0() {
+        Divisor32::new(0);
+    }
+
+    #[test]
+    fn step0() {
+        let mut x: u32 = 0x9e37_79b9;
+        for &d in &[
+            1,
+            2,
+            3,
+            7,
+            10,
+            1_000_000_000,
+            0x8000_0000,
+            0xffff_fffb,
+            u32::MAX,
+        ] {
+            let dv = Divisor32::new(d);
+            assert_eq!(dv.divisor(), d);
+            for _ in 0..1000 {
+                let r = x % d;
+                let u = (r as u64) << 32 | x.rotate_left(7) as u64;
+                let want = ((u / d as u64) as u32, (u % d as u64) as u32);
+                assert_eq!(dv.step(r, x.rotate_left(7)), want);
+                x = x.wrapping_mul(0x0019_660d).wrapping_add(0x3c6e_f35f);
+            }
+            assert_eq!(dv.step(d - 1, u32::MAX), {
+                let u = ((d - 1) as u64) << 32 | 0xffff_ffff;
+                ((u / d as u64) as u32, (u % d as u64) as u32)
+            });
+        }
+    }
+
+    #[test]
+    fn div_rem0() {
+        let x = from_u32(0).augment().augment().augment().max_value();
+        let x = x.subb32(12345, false).0;
+        for &d in &[1, 10, 1_000_000_000, u32::MAX] {
+            let dv = Divisor32::new(d);
+            assert_eq!(dv.div_rem(x), x.divr32(d));
+            assert_eq!(x / dv, x.divr32(d).0);
+            assert_eq!(x % dv, x.divr32(d).1);
+            assert_eq!(from_u32(43) / dv, from_u32(43).divr32(d).0);
+            assert_eq!(from_u32(43) % dv, 43 % d);
+        }
+    }
+}
@@ -0,0 +1,456 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Short Weierstrass curves y^2 = x^3 + ax + b over a prime field, with
+// affine and Jacobian points. Scalar multiplication is a Montgomery
+// ladder on projective coordinates using the complete addition formulas
+// of Renes, Costello and Batina, so the sequence of field operations
+// does not depend on the scalar. The formulas are complete for curves
+// without points of order two, which includes every prime-order curve.
+// The ladder runs on its own Montgomery form field arithmetic, built on
+// mont_mul_ct and carry-selected sums, and inverts Z by Fermat's little
+// theorem, so that no step branches on a secret value.
+
+use crate::fp::Fp;
+use crate::montgomery::Montgomery;
+use crate::{ModContext, Uintz, Uz};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Affine<T: Uintz, M> {
+    Infinity,
+    Point(Fp<T, M>, Fp<T, M>),
+}
+
+// (X, Y, Z) stands for (X / Z^2, Y / Z^3); Z = 0 is the point at infinity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jacobian<T: Uintz, M> {
+    x: Fp<T, M>,
+    y: Fp<T, M>,
+    z: Fp<T, M>,
+}
+
+// (X, Y, Z) stands for (X / Z, Y / Z); (0, 1, 0) is the point at infinity.
+// Coordinates are in the Montgomery form of a Field.
+#[derive(Clone, Copy)]
+struct Projective<T: Uintz> {
+    x: Uz<T>,
+    y: Uz<T>,
+    z: Uz<T>,
+}
+
+#[cfg(test)]
+thread_local! {
+    // Field operations of the ladder in order, for the tests to compare.
+    static TRACE: std::cell::RefCell<Vec<u8>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+fn trace(_op: u8) {
+    #[cfg(test)]
+    TRACE.with(|t| t.borrow_mut().push(_op));
+}
+
+// Arithmetic modulo an odd n with no branch on the operands.
+#[derive(Clone, Copy)]
+struct Field<T: Uintz> {
+    n: Uz<T>,
+    mont: Montgomery<T>,
+}
+
+impl<T: Uintz> Field<T>
+where
+    Uz<T>: Uintz,
+{
+    fn new(n: Uz<T>) -> Self {
+        Field {
+            n,
+            mont: Montgomery::new(n).expect("scalar multiplication requires an odd modulus"),
+        }
+    }
+
+    fn encode(&self, x: Uz<T>) -> Uz<T> {
+        self.mont.to_mont_ct(x)
+    }
+
+    fn decode(&self, x: Uz<T>) -> Uz<T> {
+        self.mont.from_mont_ct(x)
+    }
+
+    fn add(&self, a: Uz<T>, b: Uz<T>) -> Uz<T> {
+        trace(b'+');
+        let (s, carry) = a.ct_addc(b, 0);
+        let (d, borrow) = s.ct_subb(self.n, 0);
+        s.ct_select(d, carry | (borrow ^ 1))
+    }
+
+    fn sub(&self, a: Uz<T>, b: Uz<T>) -> Uz<T> {
+        trace(b'-');
+        let (d, borrow) = a.ct_subb(b, 0);
+        d.ct_select(d.ct_addc(self.n, 0).0, borrow)
+    }
+
+    fn mul(&self, a: Uz<T>, b: Uz<T>) -> Uz<T> {
+        trace(b'*');
+        self.mont.mont_mul_ct(a, b)
+    }
+
+    // a^(n - 2), which is a^-1 for a prime n and 0 for a = 0.
+    fn inv(&self, a: Uz<T>) -> Uz<T> {
+        self.mont.mont_pow_ct(a, self.n.subb32(2, false).0)
+    }
+
+    fn swap(&self, p: Projective<T>, q: Projective<T>, bit: u32) -> (Projective<T>, Projective<T>) {
+        trace(b'x');
+        let (x0, x1) = p.x.ct_conditional_swap(q.x, bit);
+        let (y0, y1) = p.y.ct_conditional_swap(q.y, bit);
+        let (z0, z1) = p.z.ct_conditional_swap(q.z, bit);
+        (
+            Projective {
+                x: x0,
+                y: y0,
+                z: z0,
+            },
+            Projective {
+                x: x1,
+                y: y1,
+                z: z1,
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Curve<T: Uintz, M> {
+    a: Fp<T, M>,
+    b: Fp<T, M>,
+    m: M,
+}
+
+impl<T: Uintz, M: ModContext<T> + Copy> Curve<T, M>
+where
+    Uz<T>: Uintz,
+{
+    pub fn new(a: Uz<T>, b: Uz<T>, m: M) -> Self {
+        Curve {
+            a: Fp::new(a, m),
+            b: Fp::new(b, m),
+            m,
+        }
+    }
+
+    pub fn point(&self, x: Uz<T>, y: Uz<T>) -> Affine<T, M> {
+        Affine::Point(Fp::new(x, self.m), Fp::new(y, self.m))
+    }
+
+    pub fn is_on_curve(&self, p: Affine<T, M>) -> bool {
+        match p {
+            Affine::Infinity => true,
+            Affine::Point(x, y) => (y * y).value() == ((x * x + self.a) * x + self.b).value(),
+        }
+    }
+
+    pub fn neg(&self, p: Affine<T, M>) -> Affine<T, M> {
+        match p {
+            Affine::Infinity => p,
+            Affine::Point(x, y) => Affine::Point(x, -y),
+        }
+    }
+
+    pub fn to_jacobian(&self, p: Affine<T, M>) -> Jacobian<T, M> {
+        let one = Fp::one(self.m);
+        match p {
+            Affine::Infinity => Jacobian {
+                x: one,
+                y: one,
+                z: Fp::zero(self.m),
+            },
+            Affine::Point(x, y) => Jacobian { x, y, z: one },
+        }
+    }
+
+    pub fn to_affine(&self, p: Jacobian<T, M>) -> Affine<T, M> {
+        match p.z.inv() {
+            None => Affine::Infinity,
+            Some(zi) => {
+                let zi2 = zi * zi;
+                Affine::Point(p.x * zi2, p.y * zi2 * zi)
+            }
+        }
+    }
+
+    pub fn double(&self, p: Jacobian<T, M>) -> Jacobian<T, M> {
+        // dbl-2007-bl; Z = 0 or Y = 0 give Z3 = 0.
+        let xx = p.x * p.x;
+        let yy = p.y * p.y;
+        let yyyy = yy * yy;
+        let zz = p.z * p.z;
+        let t = p.x + yy;
+        let s = t * t - xx - yyyy;
+        let s = s + s;
+        let m = xx + xx + xx + self.a * zz * zz;
+        let x = m * m - s - s;
+        let e = yyyy + yyyy;
+        let e = e + e;
+        let y = m * (s - x) - e - e;
+        let t = p.y + p.z;
+        Jacobian {
+            x,
+            y,
+            z: t * t - yy - zz,
+        }
+    }
+
+    pub fn add(&self, p: Jacobian<T, M>, q: Jacobian<T, M>) -> Jacobian<T, M> {
+        // add-2007-bl
+        if p.z.is_zero() {
+            return q;
+        }
+        if q.z.is_zero() {
+            return p;
+        }
+        let z1z1 = p.z * p.z;
+        let z2z2 = q.z * q.z;
+        let u1 = p.x * z2z2;
+        let u2 = q.x * z1z1;
+        let s1 = p.y * q.z * z2z2;
+        let s2 = q.y * p.z * z1z1;
+        let h = u2 - u1;
+        let r = s2 - s1;
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.double(p)
+            } else {
+                self.to_jacobian(Affine::Infinity)
+            };
+        }
+        let i = h + h;
+        let i = i * i;
+        let j = h * i;
+        let r = r + r;
+        let v = u1 * i;
+        let x = r * r - j - v - v;
+        let s1j = s1 * j;
+        let y = r * (v - x) - s1j - s1j;
+        let t = p.z + q.z;
+        Jacobian {
+            x,
+            y,
+            z: (t * t - z1z1 - z2z2) * h,
+        }
+    }
+
+    fn complete_add(
+        &self,
+        f: &Field<T>,
+        (a, b3): (Uz<T>, Uz<T>),
+        p: Projective<T>,
+        q: Projective<T>,
+    ) -> Projective<T> {
+        // Renes-Costello-Batina 2016, algorithm 1.
+        let t0 = f.mul(p.x, q.x);
+        let t1 = f.mul(p.y, q.y);
+        let t2 = f.mul(p.z, q.z);
+        let t3 = f.mul(f.add(p.x, p.y), f.add(q.x, q.y));
+        let t3 = f.sub(t3, f.add(t0, t1));
+        let t4 = f.mul(f.add(p.x, p.z), f.add(q.x, q.z));
+        let t4 = f.sub(t4, f.add(t0, t2));
+        let t5 = f.mul(f.add(p.y, p.z), f.add(q.y, q.z));
+        let t5 = f.sub(t5, f.add(t1, t2));
+        let z3 = f.add(f.mul(a, t4), f.mul(b3, t2));
+        let x3 = f.sub(t1, z3);
+        let z3 = f.add(t1, z3);
+        let y3 = f.mul(x3, z3);
+        let t1 = f.add(f.add(f.add(t0, t0), t0), f.mul(a, t2));
+        let t2 = f.mul(a, f.sub(t0, f.mul(a, t2)));
+        let t4 = f.add(f.mul(b3, t4), t2);
+        let y3 = f.add(y3, f.mul(t1, t4));
+        let x3 = f.sub(f.mul(t3, x3), f.mul(t5, t4));
+        let z3 = f.add(f.mul(t5, z3), f.mul(t3, t1));
+        Projective {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    // k * p through a ladder over every bit of the width of k, swapping
+    // the two accumulators with constant-time conditional swaps.
+    pub fn mul(&self, k: Uz<T>, p: Affine<T, M>) -> Affine<T, M> {
+        let f = Field::new(self.m.modulus());
+        let a = f.encode(self.a.value());
+        let b = f.encode(self.b.value());
+        let b3 = f.add(f.add(b, b), b);
+        let (zero, one) = (k.zero(), f.encode(k.zero().addc32(1, false).0));
+        let mut r0 = Projective {
+            x: zero,
+            y: one,
+            z: zero,
+        };
+        let mut r1 = match p {
+            Affine::Infinity => r0,
+            Affine::Point(x, y) => Projective {
+                x: f.encode(x.value()),
+                y: f.encode(y.value()),
+                z: one,
+            },
+        };
+        let mut limbs = vec![0; k.limbs()];
+        k.write_limbs(&mut limbs);
+        for i in (0..k.limbs() as u32 * 32).rev() {
+            let bit = limbs[i as usize / 32] >> (i % 32) & 1;
+            let (p0, p1) = f.swap(r0, r1, bit);
+            let s = self.complete_add(&f, (a, b3), p0, p1);
+            let d = self.complete_add(&f, (a, b3), p0, p0);
+            let (q0, q1) = f.swap(d, s, bit);
+            r0 = q0;
+            r1 = q1;
+        }
+        let zi = f.inv(r0.z);
+        let (x, y) = (f.decode(f.mul(r0.x, zi)), f.decode(f.mul(r0.y, zi)));
+        if r0.z == zero {
+            return Affine::Infinity;
+        }
+        Affine::Point(Fp::new(x, self.m), Fp::new(y, self.m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Affine, Curve, TRACE};
+    use crate::montgomery::Montgomery;
+    use crate::{from_u32, ModContext, Uintz, Uz, Uz32};
+
+    type U128 = Uz<Uz<Uz32>>;
+    type U256 = Uz<U128>;
+
+    fn hex(s: &str) -> U256 {
+        let limbs: Vec<u32> = (0..8)
+            .map(|i| u32::from_str_radix(&s[56 - 8 * i..64 - 8 * i], 16).unwrap())
+            .collect();
+        from_u32(0).augment().augment().augment().read_limbs(&limbs)
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Secp256k1P;
+
+    impl ModContext<U128> for Secp256k1P {
+        fn modulus(&self) -> U256 {
+            hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+        }
+    }
+
+    fn secp256k1() -> (Curve<U128, Secp256k1P>, Affine<U128, Secp256k1P>) {
+        let zero = from_u32(0).augment().augment().augment();
+        let c = Curve::new(zero, zero.addc32(7, false).0, Secp256k1P);
+        let g = c.point(
+            hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+        );
+        (c, g)
+    }
+
+    const ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+    #[test]
+    fn secp256k1_0() {
+        let (c, g) = secp256k1();
+        assert!(c.is_on_curve(g));
+        let g2 = c.point(
+            hex("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"),
+            hex("1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a"),
+        );
+        let jg = c.to_jacobian(g);
+        assert_eq!(c.to_affine(c.double(jg)), g2);
+        assert_eq!(c.to_affine(c.add(jg, jg)), g2);
+        let g3 = c.to_affine(c.add(c.double(jg), jg));
+        match g3 {
+            Affine::Point(x, _) => assert_eq!(
+                x.value(),
+                hex("f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9")
+            ),
+            Affine::Infinity => panic!("3G is not infinity"),
+        }
+        assert!(c.is_on_curve(g3));
+        let ng = c.to_jacobian(c.neg(g));
+        assert_eq!(c.to_affine(c.add(jg, ng)), Affine::Infinity);
+    }
+
+    #[test]
+    fn secp256k1_1() {
+        let (c, g) = secp256k1();
+        let n = hex(ORDER);
+        let k = hex("aa5e28d6a97a2479a65527f7290311a3624d4cc0fa1578598ee3c2613bf99522");
+        let kg = c.point(
+            hex("34f9460f0e4f08393d192b3c5133a6ba099aa0ad9fd54ebccfacdfa239ff49c6"),
+            hex("0b71ea9bd730fd8923f6d25a7a91e7dd7728a960686cb5a901bb419e0f2ca232"),
+        );
+        assert_eq!(c.mul(k, g), kg);
+        assert_eq!(c.mul(n, g), Affine::Infinity);
+        assert_eq!(c.mul(n.zero(), g), Affine::Infinity);
+        assert_eq!(c.mul(n.subb32(1, false).0, g), c.neg(g));
+        let g2 = c.mul(n.subb32(2, false).0, g);
+        assert_eq!(c.neg(g2), c.to_affine(c.double(c.to_jacobian(g))));
+        assert_eq!(c.mul(k, Affine::Infinity), Affine::Infinity);
+    }
+
+    #[test]
+    fn secp256k1_2() {
+        // Scalars of every Hamming weight class and bit length run the
+        // same sequence of field operations.
+        let (c, g) = secp256k1();
+        let zero = hex(ORDER).zero();
+        let mut seen = Vec::new();
+        for &k in &[
+            zero,
+            zero.addc32(1, false).0,
+            zero.addc32(0x8000_0000, false).0,
+            zero.max_value(),
+            zero.max_value().shr(1),
+            zero.addc32(1, false).0.shl(255),
+            hex(ORDER),
+            hex("aa5e28d6a97a2479a65527f7290311a3624d4cc0fa1578598ee3c2613bf99522"),
+        ] {
+            TRACE.with(|t| t.borrow_mut().clear());
+            c.mul(k, g);
+            let t = TRACE.with(|t| t.borrow().clone());
+            assert!(!t.is_empty());
+            seen.push(t);
+        }
+        assert!(seen.iter().all(|t| *t == seen[0]));
+    }
+
+    #[test]
+    fn toy0() {
+        // y^2 = x^3 + x + 1 over GF(97) has prime order 97.
+        let p = from_u32(0).augment().addc32(97, false).0;
+        let one = p.zero().addc32(1, false).0;
+        let c = Curve::new(one, one, Montgomery::new(p).unwrap());
+        let g = c.point(p.zero(), one);
+        assert!(c.is_on_curve(g));
+        let mut acc = c.to_jacobian(Affine::Infinity);
+        for k in 0..200 {
+            let want = c.to_affine(acc);
+            assert_eq!(c.mul(p.zero().addc32(k, false).0, g), want);
+            assert!(c.is_on_curve(want));
+            assert_eq!(want == Affine::Infinity, k % 97 == 0);
+            acc = c.add(acc, c.to_jacobian(g));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Integer factorization: trial division by the small primes, then
+// Pollard's rho with Brent's cycle detection on what remains, splitting
+// until every part passes the Baillie-PSW test.
+
+use crate::prime::{Primality, SMALL_PRIMES};
+use crate::Uintz;
+
+// Iterations of x^2 + c whose differences are multiplied together
+// before taking a gcd.
+const BATCH: u32 = 128;
+
+// Finds a nontrivial factor of the odd composite n using x^2 + c, or
+// None if this c only yields n itself.
+pub fn pollard_brent<T: Uintz>(n: T, c: u32) -> Option<T> {
+    let zero = n.zero();
+    let one = zero.addc32(1, false).0;
+    let c = zero.addc32(c, false).0.divr(n).1;
+    let f = |x: T| x.mul_mod(x, n).add_mod(c, n);
+    let diff = |a: T, b: T| {
+        if a >= b {
+            a.subb(b, false).0
+        } else {
+            b.subb(a, false).0
+        }
+    };
+    let (mut x, mut y, mut ys) = (zero, zero.addc32(2, false).0, zero);
+    let (mut q, mut g) = (one, one);
+    let mut r = 1u32;
+    while g == one {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+        let mut k = 0;
+        while k < r && g == one {
+            ys = y;
+            for _ in 0..BATCH.min(r - k) {
+                y = f(y);
+                q = q.mul_mod(diff(x, y), n);
+            }
+            g = q.gcd(n);
+            k += BATCH;
+        }
+        r = r.saturating_mul(2);
+    }
+    if g == n || g == zero {
+        // The batch overshot; retrace it one step at a time.
+        loop {
+            ys = f(ys);
+            g = diff(x, ys).gcd(n);
+            if g != one {
+                break;
+            }
+        }
+    }
+    if g == n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+fn split<T: Uintz>(n: T, primes: &mut Vec<T>) {
+    if n.bits() <= 1 {
+        return;
+    }
+    if n.is_prime_bpsw() {
+        primes.push(n);
+        return;
+    }
+    if n.is_perfect_square() {
+        let s = n.isqrt();
+        split(s, primes);
+        split(s, primes);
+        return;
+    }
+    let mut c = 1;
+    let d = loop {
+        if let Some(d) = pollard_brent(n, c) {
+            break d;
+        }
+        c += 1;
+    };
+    split(d, primes);
+    split(n.divr(d).0, primes);
+}
+
+// Prime factors of n in increasing order with their multiplicities.
+// Zero and one have no prime factors.
+pub fn factor<T: Uintz>(n: T) -> Vec<(T, u32)> {
+    let zero = n.zero();
+    let mut primes = Vec::new();
+    let mut m = n;
+    if m == zero {
+        return Vec::new();
+    }
+    for &p in SMALL_PRIMES.iter() {
+        loop {
+            let (q, r) = m.divr32(p);
+            if r != 0 {
+                break;
+            }
+            primes.push(zero.addc32(p, false).0);
+            m = q;
+        }
+    }
+    split(m, &mut primes);
+    primes.sort();
+    let mut factors: Vec<(T, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, k)) if *q == p => *k += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{factor, pollard_brent};
+    use crate::{from_u32, Uintz, Uz, Uz32};
+
+    fn uz64(v: u64) -> Uz<Uz32> {
+        from_u32(0)
+            .augment()
+            .read_limbs(&[v as u32, (v >> 32) as u32])
+    }
+
+    fn uz128(v: u128) -> Uz<Uz<Uz32>> {
+        let l = [
+            v as u32,
+            (v >> 32) as u32,
+            (v >> 64) as u32,
+            (v >> 96) as u32,
+        ];
+        from_u32(0).augment().augment().read_limbs(&l)
+    }
+
+    #[test]
+    fn factor0() {
+        assert_eq!(factor(from_u32(0)), vec![]);
+        assert_eq!(factor(from_u32(1)), vec![]);
+        assert_eq!(factor(from_u32(2)), vec![(from_u32(2), 1)]);
+        assert_eq!(
+            factor(from_u32(360)),
+            vec![(from_u32(2), 3), (from_u32(3), 2), (from_u32(5), 1)]
+        );
+        assert_eq!(
+            factor(from_u32(4294967291)),
+            vec![(from_u32(4294967291), 1)]
+        );
+        assert_eq!(
+            factor(from_u32(65521 * 65519)),
+            vec![(from_u32(65519), 1), (from_u32(65521), 1)]
+        );
+    }
+
+    #[test]
+    fn factor1() {
+        let p = (1u64 << 31) - 1;
+        let q = 4294967291u64;
+        assert_eq!(factor(uz64(p * q)), vec![(uz64(p), 1), (uz64(q), 1)]);
+        assert_eq!(
+            factor(uz64((1 << 32) + 1)),
+            vec![(uz64(641), 1), (uz64(6700417), 1)]
+        );
+        assert_eq!(factor(uz64(1 << 63)), vec![(uz64(2), 63)]);
+        assert_eq!(
+            factor(uz64(211 * 211 * 211 * 223)),
+            vec![(uz64(211), 3), (uz64(223), 1)]
+        );
+    }
+
+    #[test]
+    fn factor2() {
+        let f = factor(uz128(u128::MAX));
+        let want: Vec<_> = [
+            3u128,
+            5,
+            17,
+            257,
+            641,
+            65537,
+            274177,
+            6700417,
+            67280421310721,
+        ]
+        .iter()
+        .map(|&p| (uz128(p), 1))
+        .collect();
+        assert_eq!(f, want);
+        let p = 4294967311u128;
+        assert_eq!(factor(uz128(p * p * 3)), vec![(uz128(3), 1), (uz128(p), 2)]);
+        let q = 18446744073709551557u128;
+        assert_eq!(
+            factor(uz128(q * 1000003)),
+            vec![(uz128(1000003), 1), (uz128(q), 1)]
+        );
+    }
+
+    #[test]
+    fn pollard_brent0() {
+        let n = uz64(1000003 * 1000033);
+        let d = pollard_brent(n, 1).unwrap();
+        assert!(d == uz64(1000003) || d == uz64(1000033));
+    }
+}
@@ -0,0 +1,362 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Unsigned binary fixed point in Q format: the hi half of a Uz is the
+// integer part and the lo half the fraction. Results are truncated
+// toward zero to the fraction width.
+
+use crate::decimal::to_decimal;
+use crate::{Uintz, Uz};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed<T: Uintz> {
+    v: Uz<T>,
+}
+
+impl<T: Uintz> Fixed<T>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    pub fn from_bits(v: Uz<T>) -> Self {
+        Fixed { v }
+    }
+
+    pub fn from_int(n: T) -> Self {
+        Fixed {
+            v: Uz {
+                hi: n,
+                lo: n.zero(),
+            },
+        }
+    }
+
+    pub fn to_bits(self) -> Uz<T> {
+        self.v
+    }
+
+    pub fn int_part(self) -> T {
+        self.v.hi
+    }
+
+    pub fn frac_part(self) -> T {
+        self.v.lo
+    }
+
+    fn frac_bits(self) -> u32 {
+        self.v.lo.limbs() as u32 * 32
+    }
+
+    // self shifted up by the fraction width, on a double-width integer.
+    fn shifted(self) -> Uz<Uz<T>> {
+        let zero = self.v.lo.zero();
+        Uz {
+            hi: Uz {
+                hi: zero,
+                lo: self.v.hi,
+            },
+            lo: Uz {
+                hi: self.v.lo,
+                lo: zero,
+            },
+        }
+    }
+
+    // The middle halves of the double-width product; None on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let (lo, hi) = self.v.mulc(other.v, self.v.zero());
+        if hi.hi != hi.hi.zero() {
+            return None;
+        }
+        Some(Fixed {
+            v: Uz {
+                hi: hi.lo,
+                lo: lo.hi,
+            },
+        })
+    }
+
+    // None on overflow or a zero divisor.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.v == other.v.zero() {
+            return None;
+        }
+        let d = Uz {
+            hi: other.v.zero(),
+            lo: other.v,
+        };
+        let q = self.shifted().divr(d).0;
+        if q.hi != q.hi.zero() {
+            return None;
+        }
+        Some(Fixed { v: q.lo })
+    }
+
+    pub fn recip(self) -> Option<Self> {
+        Self::from_int(self.v.lo.zero().addc32(1, false).0).checked_div(self)
+    }
+
+    pub fn sqrt(self) -> Self {
+        // sqrt(v * 2^w) is below 2^(3w/2), so it fits the lo half.
+        Fixed {
+            v: self.shifted().isqrt().lo,
+        }
+    }
+
+    // None for negative, non-finite or too large values; the fraction is
+    // truncated to the available bits.
+    pub fn from_f64(x: f64, proto: Uz<T>) -> Option<Self> {
+        if !x.is_finite() || x < 0.0 {
+            return None;
+        }
+        let bits = x.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i32;
+        let frac = bits & ((1 << 52) - 1);
+        let (mant, e) = if exp == 0 {
+            (frac, -1074)
+        } else {
+            (frac | 1 << 52, exp - 1075)
+        };
+        let mut limbs = vec![0; proto.limbs()];
+        limbs[0] = mant as u32;
+        limbs[1] = (mant >> 32) as u32;
+        let m = proto.read_limbs(&limbs);
+        let shift = e + proto.lo.limbs() as i32 * 32;
+        let v = if shift < 0 {
+            m.shr(-shift as u32)
+        } else if m.bits() + shift as u32 > proto.limbs() as u32 * 32 {
+            return None;
+        } else {
+            m.shl(shift as u32)
+        };
+        Some(Fixed { v })
+    }
+
+    // Nearest f64 to the value.
+    pub fn to_f64(self) -> f64 {
+        let n = self.v.bits();
+        let s = n.saturating_sub(64);
+        let top = self.v.shr(s);
+        let mut limbs = vec![0; top.limbs()];
+        top.write_limbs(&mut limbs);
+        let mut x = (limbs[1] as u64) << 32 | limbs[0] as u64;
+        // A sticky bit for the discarded ones keeps the rounding exact.
+        if top.shl(s) != self.v {
+            x |= 1;
+        }
+        // Two steps, so that neither power of two leaves the f64 range
+        // before the product would.
+        let e = s as i32 - self.frac_bits() as i32;
+        x as f64 * 2f64.powi(e / 2) * 2f64.powi(e - e / 2)
+    }
+
+    // The integer part and exactly digits decimals, truncated.
+    pub fn to_decimal_string(self, digits: usize) -> String {
+        let mut s = to_decimal(self.v.hi);
+        if digits == 0 {
+            return s;
+        }
+        s.push('.');
+        let mut f = self.v.lo;
+        for _ in 0..digits {
+            let (lo, d) = f.mulc32(10, f.zero());
+            s.push(std::char::from_digit(d.lo32(), 10).unwrap());
+            f = lo;
+        }
+        s
+    }
+}
+
+impl<T: Uintz> Add for Fixed<T>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match self.v.addc(other.v, false) {
+            (v, false) => Fixed { v },
+            _ => panic!("attempt to add with overflow"),
+        }
+    }
+}
+
+impl<T: Uintz> Sub for Fixed<T>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        match self.v.subb(other.v, false) {
+            (v, false) => Fixed { v },
+            _ => panic!("attempt to subtract with overflow"),
+        }
+    }
+}
+
+impl<T: Uintz> Mul for Fixed<T>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl<T: Uintz> Div for Fixed<T>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.v == other.v.zero() {
+            panic!("attempt to divide by zero");
+        }
+        self.checked_div(other)
+            .expect("attempt to divide with overflow")
+    }
+}
+
+// The formatter's precision gives the number of decimals; by default,
+// enough to tell apart any two values.
+impl<T: Uintz> fmt::Display for Fixed<T>
+where
+    Uz<T>: Uintz,
+    Uz<Uz<T>>: Uintz,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = f
+            .precision()
+            .unwrap_or((self.frac_bits() as usize * 30103).div_ceil(100_000) + 1);
+        f.pad_integral(true, "", &self.to_decimal_string(digits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fixed;
+    use crate::{from_u32, Uintz, Uz, Uz32};
+
+    type Q32 = Fixed<Uz32>;
+    type Q64 = Fixed<Uz<Uz32>>;
+    type Q128 = Fixed<Uz<Uz<Uz32>>>;
+
+    fn q32(x: f64) -> Q32 {
+        Q32::from_f64(x, from_u32(0).augment()).unwrap()
+    }
+
+    fn q64(x: f64) -> Q64 {
+        Q64::from_f64(x, from_u32(0).augment().augment()).unwrap()
+    }
+
+    fn q128(x: f64) -> Q128 {
+        Q128::from_f64(x, from_u32(0).augment().augment().augment()).unwrap()
+    }
+
+    #[test]
+    fn f64_0() {
+        for &x in &[0.0, 1.5, 0.375, 12345.6875, 4294967295.5] {
+            assert_eq!(q32(x).to_f64(), x);
+            assert_eq!(q64(x).to_f64(), x);
+            assert_eq!(q128(x).to_f64(), x);
+        }
+        assert_eq!(q32(0.1).to_bits().lo32(), 429496729);
+        assert_eq!(q64(0.1).to_f64(), 0.1);
+        assert_eq!(q64(1e-30).to_f64(), 0.0);
+        assert!(q128(1e-30).to_f64() > 0.99e-30);
+        let tiny = Q32::from_bits(from_u32(1).augment());
+        assert_eq!(tiny.to_f64(), 1.0 / 4294967296.0);
+        let p = from_u32(0).augment();
+        assert_eq!(Q32::from_f64(4294967296.0, p), None);
+        assert_eq!(Q32::from_f64(-1.0, p), None);
+        assert_eq!(Q32::from_f64(f64::NAN, p), None);
+        assert_eq!(Q32::from_f64(f64::INFINITY, p), None);
+        assert_eq!(q32(7.25).int_part(), from_u32(7));
+        assert_eq!(q32(7.25).frac_part(), from_u32(1 << 30));
+        assert_eq!(Q32::from_int(from_u32(7)), q32(7.0));
+    }
+
+    #[test]
+    fn arith0() {
+        assert_eq!(q32(1.5) * q32(2.25), q32(3.375));
+        assert_eq!(q32(3.375) / q32(1.5), q32(2.25));
+        assert_eq!(q32(1.5) + q32(2.25) - q32(0.75), q32(3.0));
+        assert_eq!(q64(4.0).recip(), Some(q64(0.25)));
+        assert_eq!(q64(0.0).recip(), None);
+        assert_eq!(q32(0.0).checked_div(q32(0.0)), None);
+        assert_eq!(q32(65536.0).checked_mul(q32(65536.0)), None);
+        assert_eq!(
+            q32(65535.0).checked_mul(q32(65537.0)),
+            Some(q32(4294967295.0))
+        );
+        assert_eq!(q32(65536.0).checked_div(q32(1.0 / 65536.0)), None);
+        assert_eq!(q64(6.25).sqrt(), q64(2.5));
+        assert_eq!(q64(0.0).sqrt(), q64(0.0));
+        // 1/3 truncated, times 3, is one unit short of 1.
+        let third = q64(1.0) / q64(3.0);
+        assert_eq!(
+            q64(1.0) - third * q64(3.0),
+            Q64::from_bits(third.to_bits().zero().addc32(1, false).0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn div0() {
+        let _ = q32(1.0) / q32(0.0);
+    }
+
+    #[test]
+    fn format0() {
+        assert_eq!(format!("{:.3}", q32(1.5)), "1.500");
+        assert_eq!(format!("{:.0}", q32(1.5)), "1");
+        assert_eq!(q32(1.5).to_string(), "1.50000000000");
+        assert_eq!(format!("{:>8.2}", q32(1.5)), "    1.50");
+        assert_eq!(format!("{:<6.1}|", q32(1.5)), "1.5   |");
+        assert_eq!(format!("{:06.2}", q32(1.5)), "001.50");
+        assert_eq!(q32(0.1).to_decimal_string(10), "0.0999999998");
+        assert_eq!(
+            (q64(1.0) / q64(3.0)).to_decimal_string(19),
+            "0.3333333333333333333"
+        );
+        assert_eq!(q32(2.0).sqrt().to_decimal_string(9), "1.414213562");
+        assert_eq!(
+            q64(2.0).sqrt().to_decimal_string(18),
+            "1.414213562373095048"
+        );
+        assert_eq!(
+            q128(2.0).sqrt().to_decimal_string(30),
+            "1.414213562373095048801688724209"
+        );
+        let max = Q64::from_bits(from_u32(0).augment().augment().max_value());
+        assert_eq!(max.to_decimal_string(2), "18446744073709551615.99");
+    }
+}
@@ -0,0 +1,355 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Prime field elements. The modulus comes from a ModContext carried
+// alongside each element: either a Montgomery or Barrett context, for
+// moduli known at run time, or a zero-sized type returning a constant.
+// Elements are held in the representation of that value (Montgomery
+// form for a Montgomery context) and only decoded on the way out.
+
+use crate::quadratic::{jacobi, sqrt_mod};
+use crate::{ModContext, Uintz, Uz};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fp<T: Uintz, M> {
+    v: Uz<T>,
+    m: M,
+}
+
+impl<T: Uintz, M: ModContext<T> + Copy> Fp<T, M>
+where
+    Uz<T>: Uintz,
+{
+    pub fn new(v: Uz<T>, m: M) -> Self {
+        Fp { v: m.encode(v), m }
+    }
+
+    pub fn zero(m: M) -> Self {
+        Fp {
+            v: m.modulus().zero(),
+            m,
+        }
+    }
+
+    pub fn one(m: M) -> Self {
+        Fp { v: m.one(), m }
+    }
+
+    pub fn value(self) -> Uz<T> {
+        self.m.decode(self.v)
+    }
+
+    pub fn modulus(self) -> Uz<T> {
+        self.m.modulus()
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.v == self.v.zero()
+    }
+
+    pub fn inv(self) -> Option<Self> {
+        self.value()
+            .inv_mod(self.m.modulus())
+            .map(|v| Fp::new(v, self.m))
+    }
+
+    pub fn pow(self, exp: Uz<T>) -> Self {
+        Fp {
+            v: self.m.pow(self.v, exp),
+            m: self.m,
+        }
+    }
+
+    pub fn is_square(self) -> bool {
+        jacobi(self.value(), self.m.modulus()) != -1
+    }
+
+    pub fn sqrt(self) -> Option<Self> {
+        sqrt_mod(self.value(), self.m.modulus()).map(|v| Fp::new(v, self.m))
+    }
+
+    // Both operands of a binary operation must share one modulus.
+    fn check(self, other: Self) -> M {
+        assert!(self.m.modulus() == other.m.modulus(), "mismatched moduli");
+        self.m
+    }
+
+    pub fn ct_select(self, other: Self, choice: u32) -> Self {
+        Fp {
+            v: self.v.ct_select(other.v, choice),
+            m: self.m,
+        }
+    }
+
+    pub fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (v, w) = self.v.ct_conditional_swap(other.v, choice);
+        (Fp { v, m: self.m }, Fp { v: w, m: self.m })
+    }
+
+    // Big-endian bytes, as many as the width of Uz<T>.
+    pub fn to_bytes(self) -> Vec<u8> {
+        let v = self.value();
+        let mut limbs = vec![0; v.limbs()];
+        v.write_limbs(&mut limbs);
+        limbs.iter().rev().flat_map(|l| l.to_be_bytes()).collect()
+    }
+
+    // Inverse of to_bytes; None unless bytes has the exact width and
+    // holds a value below the modulus.
+    pub fn from_bytes(bytes: &[u8], m: M) -> Option<Self> {
+        let p = m.modulus();
+        if bytes.len() != p.limbs() * 4 {
+            return None;
+        }
+        let limbs: Vec<u32> = bytes
+            .rchunks(4)
+            .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        let v = p.read_limbs(&limbs);
+        if v < p {
+            Some(Fp::new(v, m))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Uintz, M: ModContext<T> + Copy> Add for Fp<T, M>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let m = self.check(other);
+        Fp {
+            v: self.v.add_mod(other.v, m.modulus()),
+            m,
+        }
+    }
+}
+
+impl<T: Uintz, M: ModContext<T> + Copy> Sub for Fp<T, M>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let m = self.check(other);
+        Fp {
+            v: self.v.sub_mod(other.v, m.modulus()),
+            m,
+        }
+    }
+}
+
+impl<T: Uintz, M: ModContext<T> + Copy> Mul for Fp<T, M>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let m = self.check(other);
+        Fp {
+            v: m.mul(self.v, other.v),
+            m,
+        }
+    }
+}
+
+impl<T: Uintz, M: ModContext<T> + Copy> Div for Fp<T, M>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let m = self.check(other);
+        let inv = other.inv().expect("attempt to divide by zero");
+        Fp {
+            v: m.mul(self.v, inv.v),
+            m,
+        }
+    }
+}
+
+impl<T: Uintz, M: ModContext<T> + Copy> Neg for Fp<T, M>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Fp {
+            v: self.v.zero().sub_mod(self.v, self.m.modulus()),
+            m: self.m,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fp;
+    use crate::barrett::Barrett;
+    use crate::montgomery::Montgomery;
+    use crate::{from_u32, ModContext, Uintz, Uz, Uz32};
+
+    type U64 = Uz<Uz32>;
+    type U256 = Uz<Uz<Uz<Uz32>>>;
+
+    fn uz64(v: u64) -> U64 {
+        from_u32(0)
+            .augment()
+            .read_limbs(&[v as u32, (v >> 32) as u32])
+    }
+
+    fn uz256(v: &[u32]) -> U256 {
+        from_u32(0).augment().augment().augment().read_limbs(v)
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P61;
+
+    impl ModContext<Uz32> for P61 {
+        fn modulus(&self) -> U64 {
+            uz64((1 << 61) - 1)
+        }
+    }
+
+    // 2^255 - 19
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P25519;
+
+    impl ModContext<Uz<Uz<Uz32>>> for P25519 {
+        fn modulus(&self) -> U256 {
+            uz256(&[
+                u32::MAX - 18,
+                u32::MAX,
+                u32::MAX,
+                u32::MAX,
+                u32::MAX,
+                u32::MAX,
+                u32::MAX,
+                0x7fff_ffff,
+            ])
+        }
+    }
+
+    fn laws<T, M>(a: Fp<T, M>, b: Fp<T, M>)
+    where
+        T: Uintz + std::fmt::Debug,
+        M: ModContext<T> + Copy + PartialEq + std::fmt::Debug,
+        Uz<T>: Uintz,
+    {
+        let (zero, one) = (Fp::zero(a.m), Fp::one(a.m));
+        assert_eq!(a + b - b, a);
+        assert_eq!(a + -a, zero);
+        assert_eq!(a * (b + one), a * b + a);
+        assert_eq!(a * b / b, a);
+        assert_eq!(a * a.inv().unwrap(), one);
+        assert_eq!(a.pow(a.modulus().subb32(1, false).0), one);
+        assert_eq!(a.pow(a.modulus()), a);
+        let s = a * a;
+        assert!(s.is_square());
+        let r = s.sqrt().unwrap();
+        assert!(r == a || r == -a);
+        assert_eq!(Fp::from_bytes(&a.to_bytes(), a.m), Some(a));
+        assert!(zero.is_zero());
+        assert!(zero.inv().is_none());
+        assert_eq!(a.ct_select(b, 0), a);
+        assert_eq!(a.ct_select(b, 1), b);
+        assert_eq!(a.ct_conditional_swap(b, 1), (b, a));
+    }
+
+    #[test]
+    fn runtime0() {
+        let p = Montgomery::new(uz64((1 << 61) - 1)).unwrap();
+        let a = Fp::new(uz64(0x0123_4567_89ab_cdef), p);
+        let b = Fp::new(uz64(u64::MAX), p);
+        assert_eq!(b.value(), uz64(u64::MAX % ((1 << 61) - 1)));
+        laws(a, b);
+        laws(b, a);
+        assert_eq!(Fp::new(uz64(3), p).to_bytes(), vec![0, 0, 0, 0, 0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn runtime1() {
+        let p = Barrett::new(uz64((1 << 61) - 1)).unwrap();
+        let a = Fp::new(uz64(0x0123_4567_89ab_cdef), p);
+        let b = Fp::new(uz64(u64::MAX), p);
+        laws(a, b);
+        let m = Montgomery::new(uz64((1 << 61) - 1)).unwrap();
+        let (x, y) = (Fp::new(a.value(), m), Fp::new(b.value(), m));
+        assert_eq!((a * b).value(), (x * y).value());
+        assert_eq!((a / b).value(), (x / y).value());
+    }
+
+    #[test]
+    fn constant0() {
+        assert_eq!(std::mem::size_of::<Fp<Uz32, P61>>(), 8);
+        let a = Fp::new(uz64(0x0123_4567_89ab_cdef), P61);
+        let b = Fp::new(uz64(7), P61);
+        laws(a, b);
+        let m = Montgomery::new(P61.modulus()).unwrap();
+        let (x, y) = (Fp::new(a.value(), m), Fp::new(b.value(), m));
+        assert_eq!((a / b).value(), (x / y).value());
+        assert_eq!((a * b).value(), (x * y).value());
+    }
+
+    #[test]
+    fn constant1() {
+        let a = Fp::new(uz256(&[1, 2, 3, 4, 5, 6, 7, 8]), P25519);
+        let b = Fp::new(uz256(&[9; 8]), P25519);
+        laws(a, b);
+        // 2 is a non-residue modulo 2^255 - 19.
+        let two = Fp::new(uz256(&[2, 0, 0, 0, 0, 0, 0, 0]), P25519);
+        assert!(!two.is_square());
+        assert!(two.sqrt().is_none());
+    }
+
+    #[test]
+    fn bytes0() {
+        let p = P25519.modulus();
+        let mut bytes = Fp::new(p.subb32(1, false).0, P25519).to_bytes();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(bytes[0], 0x7f);
+        assert_eq!(bytes[31], 0xec);
+        bytes[31] = 0xed;
+        assert!(Fp::from_bytes(&bytes, P25519).is_none());
+        assert!(Fp::from_bytes(&bytes[1..], P25519).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn div0() {
+        let _ = Fp::one(P61) / Fp::zero(P61);
+    }
+
+    #[test]
+    #[should_panic(expected = "mismatched moduli")]
+    fn mismatch0() {
+        let p = Montgomery::new(uz64(97)).unwrap();
+        let q = Montgomery::new(uz64(101)).unwrap();
+        let _ = Fp::one(p) + Fp::one(q);
+    }
+}
@@ -0,0 +1,263 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Binary extension fields GF(2^n) = GF(2)[x] / (x^n + p(x)), where p
+// holds the terms of the irreducible polynomial below x^n, so that n may
+// use the whole width of Uz<T>.
+
+use crate::{Uintz, Uz};
+use std::ops::{Add, Div, Mul, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gf2n<T: Uintz> {
+    v: Uz<T>,
+    poly: Uz<T>,
+    n: u32,
+}
+
+impl<T: Uintz> Gf2n<T>
+where
+    Uz<T>: Uintz,
+{
+    pub fn new(v: Uz<T>, n: u32, poly: Uz<T>) -> Self {
+        assert!(n > 0 && n <= v.limbs() as u32 * 32 && poly.bits() <= n);
+        let f = Gf2n { v, poly, n };
+        Gf2n {
+            v: f.reduce(v, v.zero()),
+            ..f
+        }
+    }
+
+    pub fn zero(n: u32, poly: Uz<T>) -> Self {
+        Self::new(poly.zero(), n, poly)
+    }
+
+    pub fn one(n: u32, poly: Uz<T>) -> Self {
+        Self::new(poly.zero().addc32(1, false).0, n, poly)
+    }
+
+    pub fn value(self) -> Uz<T> {
+        self.v
+    }
+
+    pub fn degree(self) -> u32 {
+        self.n
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.v == self.v.zero()
+    }
+
+    // Reduces lo + hi x^W, of degree below n + W, modulo x^n + p.
+    fn reduce(self, lo: Uz<T>, hi: Uz<T>) -> Uz<T> {
+        let w = lo.limbs() as u32 * 32;
+        let n = self.n;
+        let (mut lo, mut hi) = (lo, hi);
+        while hi != hi.zero() || lo.bits() > n {
+            let h = lo.shr(n).xor(hi.shl(w - n));
+            let l = lo.shl(w - n).shr(w - n);
+            let (tl, th) = h.clmul_wide(self.poly);
+            lo = l.xor(tl);
+            hi = th;
+        }
+        lo
+    }
+
+    pub fn square(self) -> Self {
+        let (lo, hi) = self.v.clmul_wide(self.v);
+        Gf2n {
+            v: self.reduce(lo, hi),
+            ..self
+        }
+    }
+
+    pub fn pow(self, exp: Uz<T>) -> Self {
+        let mut r = Self::one(self.n, self.poly);
+        for i in (0..exp.bits()).rev() {
+            r = r.square();
+            if exp.shr(i).lo32() & 1 == 1 {
+                r = r * self;
+            }
+        }
+        r
+    }
+
+    // Itoh-Tsujii: a^-1 = (a^(2^(n-1) - 1))^2, building a^(2^k - 1) along
+    // the binary expansion of n - 1.
+    pub fn inv(self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let m = self.n - 1;
+        if m == 0 {
+            return Some(self);
+        }
+        let (mut b, mut k) = (self, 1);
+        for i in (0..31 - m.leading_zeros()).rev() {
+            let mut t = b;
+            for _ in 0..k {
+                t = t.square();
+            }
+            b = t * b;
+            k *= 2;
+            if m >> i & 1 == 1 {
+                b = b.square() * self;
+                k += 1;
+            }
+        }
+        Some(b.square())
+    }
+}
+
+impl<T: Uintz> Add for Gf2n<T>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Gf2n {
+            v: self.v.xor(other.v),
+            ..self
+        }
+    }
+}
+
+impl<T: Uintz> Sub for Gf2n<T>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Gf2n {
+            v: self.v.xor(other.v),
+            ..self
+        }
+    }
+}
+
+impl<T: Uintz> Mul for Gf2n<T>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (lo, hi) = self.v.clmul_wide(other.v);
+        Gf2n {
+            v: self.reduce(lo, hi),
+            ..self
+        }
+    }
+}
+
+impl<T: Uintz> Div for Gf2n<T>
+where
+    Uz<T>: Uintz,
+{
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let inv = other.inv().expect("attempt to divide by zero");
+        let (lo, hi) = self.v.clmul_wide(inv.v);
+        Gf2n {
+            v: self.reduce(lo, hi),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gf2n;
+    use crate::{from_u32, Uintz, Uz};
+
+    fn hex<T: Uintz>(zero: T, s: &str) -> T {
+        let mut limbs = vec![0; zero.limbs()];
+        for (i, l) in limbs.iter_mut().enumerate() {
+            let end = s.len().saturating_sub(8 * i);
+            let start = end.saturating_sub(8);
+            if end > 0 {
+                *l = u32::from_str_radix(&s[start..end], 16).unwrap();
+            }
+        }
+        zero.read_limbs(&limbs)
+    }
+
+    fn laws<T: Uintz + std::fmt::Debug>(a: Gf2n<T>, b: Gf2n<T>, c: Gf2n<T>)
+    where
+        Uz<T>: Uintz,
+    {
+        let one = Gf2n::one(a.degree(), a.poly);
+        assert_eq!(a + a, Gf2n::zero(a.degree(), a.poly));
+        assert_eq!(a - b, a + b);
+        assert_eq!((a + b) * c, a * c + b * c);
+        assert_eq!(a * b, b * a);
+        assert_eq!(a.square(), a * a);
+        assert_eq!(a * a.inv().unwrap(), one);
+        assert_eq!(a * b / b, a);
+        let e =
+            a.v.zero()
+                .max_value()
+                .shr(a.v.limbs() as u32 * 32 - a.degree());
+        assert_eq!(a.pow(e), one);
+        assert_eq!(a.pow(e.addc32(1, false).0), a);
+        assert!(Gf2n::zero(a.degree(), a.poly).inv().is_none());
+    }
+
+    #[test]
+    fn gf2_8() {
+        // AES: x^8 + x^4 + x^3 + x + 1.
+        let zero = from_u32(0).augment();
+        let poly = zero.addc32(0x1b, false).0;
+        let f = |v| Gf2n::new(zero.addc32(v, false).0, 8, poly);
+        assert_eq!((f(0x57) * f(0x83)).value(), f(0xc1).value());
+        assert_eq!(f(0x53).inv(), Some(f(0xca)));
+        assert_eq!(f(0x11b), f(0));
+        laws(f(0x57), f(0x83), f(0x02));
+    }
+
+    #[test]
+    fn gf2_128() {
+        // GCM: x^128 + x^7 + x^2 + x + 1, without bit reflection.
+        let zero = from_u32(0).augment().augment().augment();
+        let poly = zero.addc32(0x87, false).0;
+        let f = |s| Gf2n::new(hex(zero, s), 128, poly);
+        let a = f("0123456789abcdeffedcba9876543210");
+        let b = f("deadbeefcafebabe0011223344556677");
+        assert_eq!(a * b, f("fa990997bd53944d1a1576f80d93b1dd"));
+        assert_eq!(a.inv(), Some(f("ac20a8a9f088c918e7a4a93e6b40984a")));
+        laws(a, b, f("87"));
+    }
+
+    #[test]
+    fn gf2_233() {
+        // x^233 + x^74 + 1, the field of sect233k1 and sect233r1.
+        let zero = from_u32(0).augment().augment().augment();
+        let poly = zero.addc32(1, false).0.xor(zero.addc32(1, false).0.shl(74));
+        let f = |s| Gf2n::new(hex(zero, s), 233, poly);
+        let a = f("1234567890abcdef00000000000000000000000000000000feedface");
+        let b = f("10000000000000000000000000000000000000000000000000000abcdef");
+        let p = f("cf48810f86804b2a280000048d159e3bf74d5cc0012524d06a1fe8c29");
+        assert_eq!(a * b, p);
+        laws(a, b, p);
+    }
+}
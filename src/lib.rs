@@ -18,9 +18,37 @@
  * along with uintz.  If not, see <http://www.gnu.org/licenses/>
  */
 
+pub mod barrett;
+pub mod crt;
+pub mod decimal;
+pub mod divisor;
+pub mod ec;
+pub mod factor;
+pub mod fixed;
+pub mod fp;
+pub mod gf2n;
+mod limbs;
+pub mod montgomery;
+mod ntt;
+pub mod prime;
+pub mod prng;
+pub mod quadratic;
+pub mod random;
+mod short;
+mod toom3;
 pub mod uz;
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::items_after_test_module,
+    clippy::legacy_numeric_constants
+)]
 pub mod uz32;
-//pub mod uzz;
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::legacy_numeric_constants,
+    clippy::type_complexity
+)]
+pub mod uzz;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub struct Uz32 {
@@ -33,7 +61,9 @@ pub struct Uz<T: Uintz> {
     lo: T,
 }
 
-pub trait Uintz {
+pub trait Uintz: Copy + Ord {
+    fn add_mod(self, other: Self, modulus: Self) -> Self;
+
     fn addc(self, other: Self, carry: bool) -> (Self, bool)
     where
         Self: std::marker::Sized;
@@ -46,10 +76,95 @@ pub trait Uintz {
     where
         Self: std::marker::Sized;
 
+    fn bits(self) -> u32;
+
+    fn checked_pow(self, exp: u32) -> Option<Self>
+    where
+        Self: std::marker::Sized;
+
+    fn cldivr(self, divisor: Self) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
+    fn clmul(self, other: Self) -> Self;
+
+    fn clmul_wide(self, other: Self) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
+    // The ct_ methods do not branch on their operands; gen/ctasm checks a
+    // release build for conditional jumps.
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32)
+    where
+        Self: std::marker::Sized;
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
+    fn ct_eq(self, other: Self) -> u32;
+
+    fn ct_lt(self, other: Self) -> u32;
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
+    fn ct_select(self, other: Self, choice: u32) -> Self;
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32)
+    where
+        Self: std::marker::Sized;
+
+    fn divr(self, divisor: Self) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
     fn divr32(self, divisor: u32) -> (Self, u32)
     where
         Self: std::marker::Sized;
 
+    fn divr32_pre(self, divisor: &divisor::Divisor32, remainder: u32) -> (Self, u32)
+    where
+        Self: std::marker::Sized;
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32)
+    where
+        Self: std::marker::Sized;
+
+    // Returns (g, x, y, odd), g being gcd(self, other) and x, y unsigned
+    // cofactors: self x - other y = g when odd is false, and
+    // other y - self x = g when odd is true.
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool)
+    where
+        Self: std::marker::Sized;
+
+    fn gcd(self, other: Self) -> Self;
+
+    fn inv_mod(self, modulus: Self) -> Option<Self>
+    where
+        Self: std::marker::Sized;
+
+    fn iroot(self, n: u32) -> Self;
+
+    fn is_perfect_power(self) -> bool;
+
+    fn is_perfect_square(self) -> bool;
+
+    fn isqrt(self) -> Self;
+
+    fn isqrt_rem(self) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
+    fn lcm(self, other: Self) -> Option<Self>
+    where
+        Self: std::marker::Sized;
+
+    fn limbs(self) -> usize;
+
+    fn lo32(self) -> u32;
+
     fn max_value(self) -> Self;
 
     fn mulc(self, other: Self, carry: Self) -> (Self, Self)
@@ -60,6 +175,38 @@ pub trait Uintz {
     where
         Self: std::marker::Sized;
 
+    fn mul_hi(self, other: Self) -> Self;
+
+    fn mul_lo(self, other: Self) -> Self;
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self;
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
+    fn pow(self, exp: u32) -> Self;
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self;
+
+    fn pow_wide(self, exp: Self) -> Self;
+
+    fn read_limbs(self, src: &[u32]) -> Self;
+
+    fn saturating_pow(self, exp: u32) -> Self;
+
+    fn shl(self, n: u32) -> Self;
+
+    fn shr(self, n: u32) -> Self;
+
+    fn square(self) -> Self;
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self;
+
     fn subb(self, other: Self, borrow: bool) -> (Self, bool)
     where
         Self: std::marker::Sized;
@@ -68,9 +215,69 @@ pub trait Uintz {
     where
         Self: std::marker::Sized;
 
+    fn trailing_zeros(self) -> u32;
+
+    fn widening_square(self) -> (Self, Self)
+    where
+        Self: std::marker::Sized;
+
+    fn wrapping_pow(self, exp: u32) -> Self;
+
+    fn write_limbs(self, dst: &mut [u32]);
+
+    fn xor(self, other: Self) -> Self;
+
     fn zero(self) -> Self;
 }
 
+// Common interface of modular reduction contexts. Values are encoded
+// into the context's internal representation, operated on, and decoded
+// back. Only the modulus is required: the provided methods keep plain
+// residues and reduce by division, which suits a zero-sized type
+// returning a constant; precomputed contexts override them all.
+pub trait ModContext<T: Uintz> {
+    fn decode(&self, x: Uz<T>) -> Uz<T> {
+        x
+    }
+
+    fn encode(&self, x: Uz<T>) -> Uz<T>
+    where
+        Uz<T>: Uintz,
+    {
+        x.divr(self.modulus()).1
+    }
+
+    fn modulus(&self) -> Uz<T>;
+
+    fn mul(&self, a: Uz<T>, b: Uz<T>) -> Uz<T>
+    where
+        Uz<T>: Uintz,
+    {
+        a.mul_mod(b, self.modulus())
+    }
+
+    fn one(&self) -> Uz<T>
+    where
+        Uz<T>: Uintz,
+    {
+        self.encode(self.modulus().zero().addc32(1, false).0)
+    }
+
+    fn pow(&self, a: Uz<T>, exp: Uz<T>) -> Uz<T>
+    where
+        Uz<T>: Uintz,
+    {
+        a.pow_mod(exp, self.modulus())
+    }
+
+    fn square(&self, a: Uz<T>) -> Uz<T>
+    where
+        Uz<T>: Uintz,
+    {
+        self.mul(a, a)
+    }
+}
+
 pub fn from_u32(v: u32) -> Uz32 {
     Uz32 { v }
 }
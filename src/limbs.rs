@@ -0,0 +1,357 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Helpers on little endian slices of 32 bit limbs, shared by the
+// multiplication algorithms that do not follow the hi / lo recursion.
+
+use std::cmp::Ordering;
+
+pub fn trimmed(a: &[u32]) -> &[u32] {
+    let n = a.iter().rposition(|&v| v != 0).map_or(0, |i| i + 1);
+    &a[..n]
+}
+
+pub fn cmp(a: &[u32], b: &[u32]) -> Ordering {
+    let a = trimmed(a);
+    let b = trimmed(b);
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+pub fn add_assign(acc: &mut [u32], other: &[u32]) -> bool {
+    let mut carry = 0u64;
+    for (i, a) in acc.iter_mut().enumerate() {
+        let o = other.get(i).copied().unwrap_or(0);
+        if i >= other.len() && carry == 0 {
+            break;
+        }
+        let nv = *a as u64 + o as u64 + carry;
+        *a = nv as u32;
+        carry = nv >> 32;
+    }
+    carry != 0
+}
+
+pub fn sub_assign(acc: &mut [u32], other: &[u32]) -> bool {
+    let mut borrow = 0u64;
+    for (i, a) in acc.iter_mut().enumerate() {
+        let o = other.get(i).copied().unwrap_or(0);
+        if i >= other.len() && borrow == 0 {
+            break;
+        }
+        let nv = (*a as u64).wrapping_sub(o as u64 + borrow);
+        *a = nv as u32;
+        borrow = nv >> 63;
+    }
+    borrow != 0
+}
+
+pub fn add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut r = Vec::with_capacity(a.len() + 1);
+    r.extend_from_slice(a);
+    r.push(0);
+    add_assign(&mut r, b);
+    r
+}
+
+pub fn sub(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut r = a.to_vec();
+    let borrow = sub_assign(&mut r, b);
+    debug_assert!(!borrow);
+    r
+}
+
+pub fn shl1(a: &[u32]) -> Vec<u32> {
+    let mut r = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for &v in a {
+        r.push(v << 1 | carry);
+        carry = v >> 31;
+    }
+    r.push(carry);
+    r
+}
+
+pub fn shr1(a: &[u32]) -> Vec<u32> {
+    let mut r = vec![0; a.len()];
+    let mut carry = 0;
+    for (i, &v) in a.iter().enumerate().rev() {
+        r[i] = v >> 1 | carry;
+        carry = v << 31;
+    }
+    r
+}
+
+pub fn divr32(a: &mut [u32], divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for v in a.iter_mut().rev() {
+        let nv = remainder << 32 | *v as u64;
+        *v = (nv / divisor as u64) as u32;
+        remainder = nv % divisor as u64;
+    }
+    remainder as u32
+}
+
+// Knuth's algorithm D; both results have as many limbs as the dividend.
+pub fn divrem(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let b = trimmed(b);
+    assert!(!b.is_empty(), "attempt to divide by zero");
+    let mut q = vec![0; a.len()];
+    let mut r = a.to_vec();
+    if cmp(a, b) == Ordering::Less {
+        return (q, r);
+    }
+    if b.len() == 1 {
+        q.copy_from_slice(a);
+        let rem = divr32(&mut q, b[0]);
+        r.iter_mut().for_each(|v| *v = 0);
+        r[0] = rem;
+        return (q, r);
+    }
+    let a = trimmed(a);
+    let n = b.len();
+    let m = a.len() - n;
+    let s = b[n - 1].leading_zeros();
+    let bn = shl(b, s);
+    let mut an = shl(a, s);
+    an.push(0);
+    for j in (0..=m).rev() {
+        let num = (an[j + n] as u64) << 32 | an[j + n - 1] as u64;
+        let mut qhat = num / bn[n - 1] as u64;
+        let mut rhat = num % bn[n - 1] as u64;
+        while qhat >> 32 != 0 || qhat * bn[n - 2] as u64 > (rhat << 32 | an[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += bn[n - 1] as u64;
+            if rhat >> 32 != 0 {
+                break;
+            }
+        }
+        let mut k = 0i64;
+        for i in 0..n {
+            let p = qhat * bn[i] as u64;
+            let t = an[i + j] as i64 - k - (p & 0xffff_ffff) as i64;
+            an[i + j] = t as u32;
+            k = (p >> 32) as i64 - (t >> 32);
+        }
+        let t = an[j + n] as i64 - k;
+        an[j + n] = t as u32;
+        if t < 0 {
+            qhat -= 1;
+            let carry = add_assign(&mut an[j..j + n], &bn[..n]);
+            an[j + n] = an[j + n].wrapping_add(carry as u32);
+        }
+        q[j] = qhat as u32;
+    }
+    r.iter_mut().for_each(|v| *v = 0);
+    r[..n].copy_from_slice(&shr(&an[..n + 1], s)[..n]);
+    (q, r)
+}
+
+// Polynomial division over GF(2); both results have as many limbs as
+// the dividend.
+pub fn cldivrem(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let b = trimmed(b);
+    assert!(!b.is_empty(), "attempt to divide by zero");
+    let d = b.len() * 32 - b[b.len() - 1].leading_zeros() as usize;
+    let mut q = vec![0; a.len()];
+    let mut r = a.to_vec();
+    // The divisor at every bit offset, so that each step XORs whole limbs.
+    let shifted: Vec<Vec<u32>> = (0..32).map(|s| shl(b, s)).collect();
+    for i in (d - 1..a.len() * 32).rev() {
+        if r[i / 32] >> (i % 32) & 1 == 0 {
+            continue;
+        }
+        let k = i + 1 - d;
+        q[k / 32] |= 1 << (k % 32);
+        for (x, &v) in r[k / 32..].iter_mut().zip(shifted[k % 32].iter()) {
+            *x ^= v;
+        }
+    }
+    (q, r)
+}
+
+// Shifts by less than 32 bits; shl grows the result by one limb.
+pub fn shl(a: &[u32], s: u32) -> Vec<u32> {
+    let mut r = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for &v in a {
+        r.push(v << s | carry);
+        carry = if s == 0 { 0 } else { v >> (32 - s) };
+    }
+    r.push(carry);
+    r
+}
+
+pub fn shr(a: &[u32], s: u32) -> Vec<u32> {
+    let mut r = vec![0; a.len()];
+    let mut carry = 0;
+    for (i, &v) in a.iter().enumerate().rev() {
+        r[i] = v >> s | carry;
+        carry = if s == 0 { 0 } else { v << (32 - s) };
+    }
+    r
+}
+
+pub fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut r = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let nv = x as u64 * y as u64 + r[i + j] as u64 + carry;
+            r[i + j] = nv as u32;
+            carry = nv >> 32;
+        }
+        r[i + b.len()] = carry as u32;
+    }
+    r
+}
+
+pub fn sqr(a: &[u32]) -> Vec<u32> {
+    let mut r = vec![0; 2 * a.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in a.iter().enumerate().skip(i + 1) {
+            let nv = x as u64 * y as u64 + r[i + j] as u64 + carry;
+            r[i + j] = nv as u32;
+            carry = nv >> 32;
+        }
+        r[i + a.len()] = carry as u32;
+    }
+    let mut r = shl1(&r);
+    r.pop();
+    let mut carry = 0u64;
+    for (i, &x) in a.iter().enumerate() {
+        let nv = x as u64 * x as u64 + r[2 * i] as u64 + carry;
+        r[2 * i] = nv as u32;
+        let nv = r[2 * i + 1] as u64 + (nv >> 32);
+        r[2 * i + 1] = nv as u32;
+        carry = nv >> 32;
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn add_assign0() {
+        let mut a = vec![u32::MAX, u32::MAX, 0];
+        assert!(!add_assign(&mut a, &[1]));
+        assert_eq!(a, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn sub_assign0() {
+        let mut a = vec![0, 0, 1];
+        assert!(!sub_assign(&mut a, &[1]));
+        assert_eq!(a, vec![u32::MAX, u32::MAX, 0]);
+        assert!(sub_assign(&mut a, &[0, 0, 1]));
+    }
+
+    #[test]
+    fn cmp0() {
+        assert_eq!(cmp(&[1, 0, 0], &[1]), Ordering::Equal);
+        assert_eq!(cmp(&[0, 1], &[u32::MAX]), Ordering::Greater);
+        assert_eq!(cmp(&[5, 1], &[6, 1, 0]), Ordering::Less);
+    }
+
+    #[test]
+    fn shifts0() {
+        assert_eq!(shl1(&[0x8000_0001, 1]), vec![2, 3, 0]);
+        assert_eq!(shr1(&[2, 3]), vec![0x8000_0001, 1]);
+    }
+
+    #[test]
+    fn divr32_0() {
+        let mut a = vec![1, 1];
+        assert_eq!(divr32(&mut a, 3), 2);
+        assert_eq!(a, vec![0x5555_5555, 0]);
+    }
+
+    #[test]
+    fn sqr0() {
+        let a = [u32::MAX, 7, u32::MAX, 0x8000_0000];
+        assert_eq!(sqr(&a), mul(&a, &a));
+        assert_eq!(sqr(&[u32::MAX]), vec![1, u32::MAX - 1]);
+    }
+
+    #[test]
+    fn divrem0() {
+        let (q, r) = divrem(&[7, 0, 0], &[2]);
+        assert_eq!(q, vec![3, 0, 0]);
+        assert_eq!(r, vec![1, 0, 0]);
+        let (q, r) = divrem(&[1, 2], &[3, 4, 5]);
+        assert_eq!(q, vec![0, 0]);
+        assert_eq!(r, vec![1, 2]);
+    }
+
+    #[test]
+    fn divrem1() {
+        let a = [0x1234_5678, u32::MAX, 0, 0x8000_0000, 17, u32::MAX];
+        let b = [u32::MAX, 3, 0x0000_ffff];
+        let (q, r) = divrem(&a, &b);
+        assert_eq!(cmp(&r, &b), Ordering::Less);
+        let mut p = mul(&q, &b);
+        add_assign(&mut p, &r);
+        assert_eq!(cmp(&p, &a), Ordering::Equal);
+    }
+
+    #[test]
+    fn divrem2() {
+        let a = [0, 0, 0, 0x8000_0000];
+        let b = [1, 0, 0x8000_0000];
+        let (q, r) = divrem(&a, &b);
+        let mut p = mul(&q, &b);
+        add_assign(&mut p, &r);
+        assert_eq!(cmp(&p, &a), Ordering::Equal);
+        assert_eq!(cmp(&r, &b), Ordering::Less);
+    }
+
+    #[test]
+    #[should_panic]
+    fn divrem3() {
+        divrem(&[1], &[0, 0]);
+    }
+
+    #[test]
+    fn cldivrem0() {
+        let (q, r) = cldivrem(&[50, 0], &[7, 0]);
+        assert_eq!(q, vec![11, 0]);
+        assert_eq!(r, vec![3, 0]);
+        let (q, r) = cldivrem(&[0, 1, 0x8000_0000], &[1]);
+        assert_eq!(q, vec![0, 1, 0x8000_0000]);
+        assert_eq!(r, vec![0, 0, 0]);
+        let (q, r) = cldivrem(&[0, 0, 1], &[0, 3]);
+        assert_eq!(q, vec![u32::MAX, 0, 0]);
+        assert_eq!(r, vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn mul0() {
+        assert_eq!(
+            mul(&[u32::MAX, u32::MAX], &[u32::MAX]),
+            vec![1, u32::MAX, u32::MAX - 1]
+        );
+    }
+}
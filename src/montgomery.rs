@@ -0,0 +1,365 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Montgomery reduction context for repeated multiplications modulo one
+// odd modulus, interleaving reduction with the product word by word
+// (CIOS) over the limbs.
+
+use crate::{ModContext, Uintz, Uz};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Montgomery<T: Uintz> {
+    n: Uz<T>,
+    // -n^-1 mod 2^32
+    ninv: u32,
+    // R mod n and R^2 mod n, where R = 2^(32 * limbs)
+    r1: Uz<T>,
+    r2: Uz<T>,
+}
+
+impl<T: Uintz> Montgomery<T>
+where
+    Uz<T>: Uintz,
+{
+    pub fn new(modulus: Uz<T>) -> Option<Self> {
+        let n0 = modulus.lo32();
+        if n0 & 1 == 0 {
+            return None;
+        }
+        // Newton iteration doubles the correct low bits of n0^-1 each step.
+        let mut x: u32 = 1;
+        for _ in 0..5 {
+            x = x.wrapping_mul(2u32.wrapping_sub(n0.wrapping_mul(x)));
+        }
+        let zero = modulus.zero();
+        let r1 = zero.subb(modulus, false).0.divr(modulus).1;
+        Some(Montgomery {
+            n: modulus,
+            ninv: x.wrapping_neg(),
+            r1,
+            r2: r1.mul_mod(r1, modulus),
+        })
+    }
+
+    pub fn to_mont(&self, x: Uz<T>) -> Uz<T> {
+        self.mont_mul(x.divr(self.n).1, self.r2)
+    }
+
+    pub fn from_mont(&self, x: Uz<T>) -> Uz<T> {
+        self.mont_mul(x, x.zero().addc32(1, false).0)
+    }
+
+    // CIOS over the limbs with 64 bit accumulators: returns a b R^-1,
+    // possibly plus n, and the carry above the top limb. The loops run
+    // a fixed number of times and never branch on the operands.
+    fn redc(&self, a: Uz<T>, b: Uz<T>) -> (Uz<T>, u32) {
+        let s = a.limbs();
+        let mut buf = vec![0; 4 * s + 2];
+        let (al, rest) = buf.split_at_mut(s);
+        let (bl, rest) = rest.split_at_mut(s);
+        let (nl, t) = rest.split_at_mut(s);
+        a.write_limbs(al);
+        b.write_limbs(bl);
+        self.n.write_limbs(nl);
+        for &bi in bl.iter() {
+            let mut c: u64 = 0;
+            for j in 0..s {
+                let x = t[j] as u64 + al[j] as u64 * bi as u64 + c;
+                t[j] = x as u32;
+                c = x >> 32;
+            }
+            let x = t[s] as u64 + c;
+            t[s] = x as u32;
+            t[s + 1] = (x >> 32) as u32;
+            // The low word of t + m n is zero by the choice of m.
+            let m = t[0].wrapping_mul(self.ninv);
+            let mut c = (t[0] as u64 + m as u64 * nl[0] as u64) >> 32;
+            for j in 1..s {
+                let x = t[j] as u64 + m as u64 * nl[j] as u64 + c;
+                t[j - 1] = x as u32;
+                c = x >> 32;
+            }
+            let x = t[s] as u64 + c;
+            t[s - 1] = x as u32;
+            t[s] = t[s + 1] + (x >> 32) as u32;
+        }
+        (a.read_limbs(&t[..s]), t[s])
+    }
+
+    pub fn mont_mul(&self, a: Uz<T>, b: Uz<T>) -> Uz<T> {
+        let (t, top) = self.redc(a, b);
+        if top != 0 || t >= self.n {
+            t.subb(self.n, false).0
+        } else {
+            t
+        }
+    }
+
+    pub fn mont_square(&self, a: Uz<T>) -> Uz<T> {
+        self.mont_mul(a, a)
+    }
+
+    pub fn mont_pow(&self, a: Uz<T>, exp: Uz<T>) -> Uz<T> {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut r = self.r1;
+        for i in (0..exp.bits()).rev() {
+            r = self.mont_square(r);
+            if e[i as usize / 32] >> (i % 32) & 1 != 0 {
+                r = self.mont_mul(r, a);
+            }
+        }
+        r
+    }
+
+    // As mont_mul, with no branch or memory access depending on a or b.
+    pub fn mont_mul_ct(&self, a: Uz<T>, b: Uz<T>) -> Uz<T> {
+        let (t, top) = self.redc(a, b);
+        let (s, borrow) = t.ct_subb(self.n, 0);
+        t.ct_select(s, top | (borrow ^ 1))
+    }
+
+    // As to_mont, for any x below R: x R^2 R^-1 needs no prior reduction.
+    pub fn to_mont_ct(&self, x: Uz<T>) -> Uz<T> {
+        self.mont_mul_ct(x, self.r2)
+    }
+
+    pub fn from_mont_ct(&self, x: Uz<T>) -> Uz<T> {
+        self.mont_mul_ct(x, x.zero().addc32(1, false).0)
+    }
+
+    // Montgomery ladder over every bit of the exponent's width, so that
+    // neither the exponent's value nor its length shows in the sequence
+    // of operations.
+    pub fn mont_pow_ct(&self, a: Uz<T>, exp: Uz<T>) -> Uz<T> {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let (mut r0, mut r1) = (self.r1, a);
+        for i in (0..exp.limbs() as u32 * 32).rev() {
+            let bit = e[i as usize / 32] >> (i % 32) & 1;
+            let (x, y) = r0.ct_conditional_swap(r1, bit);
+            r1 = self.mont_mul_ct(x, y);
+            r0 = self.mont_mul_ct(x, x);
+            let (x, y) = r0.ct_conditional_swap(r1, bit);
+            r0 = x;
+            r1 = y;
+        }
+        r0
+    }
+}
+
+// Constant-time modular exponentiation for an odd modulus, with the
+// exponent kept secret.
+pub fn pow_mod_ct<T: Uintz>(base: Uz<T>, exp: Uz<T>, modulus: Uz<T>) -> Uz<T>
+where
+    Uz<T>: Uintz,
+{
+    let m = Montgomery::new(modulus).expect("pow_mod_ct requires an odd modulus");
+    m.from_mont_ct(m.mont_pow_ct(m.to_mont_ct(base), exp))
+}
+
+impl<T: Uintz> ModContext<T> for Montgomery<T>
+where
+    Uz<T>: Uintz,
+{
+    fn decode(&self, x: Uz<T>) -> Uz<T> {
+        self.from_mont(x)
+    }
+
+    fn encode(&self, x: Uz<T>) -> Uz<T> {
+        self.to_mont(x)
+    }
+
+    fn modulus(&self) -> Uz<T> {
+        self.n
+    }
+
+    fn mul(&self, a: Uz<T>, b: Uz<T>) -> Uz<T> {
+        self.mont_mul(a, b)
+    }
+
+    fn one(&self) -> Uz<T> {
+        self.r1
+    }
+
+    fn pow(&self, a: Uz<T>, exp: Uz<T>) -> Uz<T> {
+        self.mont_pow(a, exp)
+    }
+
+    fn square(&self, a: Uz<T>) -> Uz<T> {
+        self.mont_square(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pow_mod_ct, Montgomery};
+    use crate::{from_u32, ModContext, Uintz, Uz, Uz32};
+
+    fn uz64(v: u64) -> Uz<Uz32> {
+        from_u32(0)
+            .augment()
+            .read_limbs(&[v as u32, (v >> 32) as u32])
+    }
+
+    fn uz256(v: &[u32]) -> Uz<Uz<Uz<Uz32>>> {
+        from_u32(0).augment().augment().augment().read_limbs(v)
+    }
+
+    #[test]
+    fn new0() {
+        assert!(Montgomery::new(uz64(10)).is_none());
+        assert!(Montgomery::new(uz64(0)).is_none());
+        let m = Montgomery::new(uz64(0xffff_ffff_ffff_ffc5)).unwrap();
+        assert_eq!(m.modulus(), uz64(0xffff_ffff_ffff_ffc5));
+        assert_eq!(m.one(), uz64(0x3b));
+        assert_eq!(m.from_mont(m.one()), uz64(1));
+    }
+
+    #[test]
+    fn roundtrip0() {
+        let m = Montgomery::new(uz64(1_000_000_007)).unwrap();
+        for &v in &[0, 1, 2, 999_999_999, 1_000_000_006, 1_000_000_008] {
+            assert_eq!(m.from_mont(m.to_mont(uz64(v))), uz64(v % 1_000_000_007));
+        }
+    }
+
+    #[test]
+    fn mont_mul0() {
+        for &n in &[
+            3u64,
+            0xffff_ffff,
+            0xffff_ffff_ffff_ffc5,
+            0x8000_0000_0000_0001,
+        ] {
+            let n = uz64(n);
+            let m = Montgomery::new(n).unwrap();
+            let mut a = uz64(0x0123_4567_89ab_cdef);
+            let mut b = uz64(0xfedc_ba98_7654_3210);
+            for _ in 0..20 {
+                let p = m.from_mont(m.mont_mul(m.to_mont(a), m.to_mont(b)));
+                assert_eq!(p, a.mul_mod(b, n));
+                let s = m.from_mont(m.mont_square(m.to_mont(a)));
+                assert_eq!(s, a.mul_mod(a, n));
+                a = a.mul_lo(b).addc32(0x9e37_79b9, false).0;
+                b = b.square().addc32(7, false).0;
+            }
+        }
+    }
+
+    #[test]
+    fn mont_mul1() {
+        // 2^255 - 19 and a max_value modulus.
+        let p = uz256(&[
+            0xffff_ffed,
+            0xffff_ffff,
+            0xffff_ffff,
+            0xffff_ffff,
+            0xffff_ffff,
+            0xffff_ffff,
+            0xffff_ffff,
+            0x7fff_ffff,
+        ]);
+        for &n in &[p, p.max_value()] {
+            let m = Montgomery::new(n).unwrap();
+            let mut a = uz256(&[1, 2, 3, 4, 5, 6, 7, 0xffff_ffff]);
+            let mut b = uz256(&[0xdead_beef, 0, 0, 0, 0, 0, 0, 0x8000_0000]);
+            for _ in 0..20 {
+                let r = m.from_mont(m.mont_mul(m.to_mont(a), m.to_mont(b)));
+                assert_eq!(r, a.mul_mod(b, n));
+                a = a.mul_lo(b).addc32(0x9e37_79b9, false).0;
+                b = b.square().addc32(7, false).0;
+            }
+        }
+    }
+
+    #[test]
+    fn mont_pow0() {
+        let n = uz256(&[0xffff_ffed, 0xffff_ffff, 0, 0, 0, 0, 0, 0x7fff_ffff]);
+        let m = Montgomery::new(n).unwrap();
+        let a = uz256(&[0x1234_5678, 0x9abc_def0, 0, 0, 5, 0, 0, 0]);
+        for &e in &[
+            uz256(&[0; 8]),
+            uz256(&[1, 0, 0, 0, 0, 0, 0, 0]),
+            uz256(&[65537, 0, 0, 0, 0, 0, 0, 0]),
+            n.subb32(2, false).0,
+        ] {
+            let r = m.from_mont(m.mont_pow(m.to_mont(a), e));
+            assert_eq!(r, a.pow_mod(e, n));
+        }
+    }
+
+    #[test]
+    fn mont_mul_ct0() {
+        let n = uz256(&[0xffff_ffed, 0xffff_ffff, 0, 0, 0, 0, 0, 0x7fff_ffff]);
+        for &n in &[n, n.max_value(), uz256(&[3, 0, 0, 0, 0, 0, 0, 0])] {
+            let m = Montgomery::new(n).unwrap();
+            let mut a = uz256(&[1, 2, 3, 4, 5, 6, 7, 0xffff_ffff]).divr(n).1;
+            let mut b = uz256(&[0xdead_beef, 0, 0, 0, 0, 0, 0, 0x8000_0000])
+                .divr(n)
+                .1;
+            for _ in 0..20 {
+                assert_eq!(m.mont_mul_ct(a, b), m.mont_mul(a, b));
+                assert_eq!(m.mont_mul_ct(a, a), m.mont_square(a));
+                a = m.mont_mul(a, b).addc32(1, false).0.divr(n).1;
+                b = m.mont_square(b).addc32(7, false).0.divr(n).1;
+            }
+        }
+    }
+
+    #[test]
+    fn pow_mod_ct0() {
+        let n = uz256(&[0xffff_ffed, 0xffff_ffff, 0, 0, 0, 0, 0, 0x7fff_ffff]);
+        let a = uz256(&[0x1234_5678, 0x9abc_def0, 0, 0, 5, 0, 0, 0]);
+        for &e in &[
+            uz256(&[0; 8]),
+            uz256(&[1, 0, 0, 0, 0, 0, 0, 0]),
+            uz256(&[65537, 0, 0, 0, 0, 0, 0, 0]),
+            n.subb32(2, false).0,
+            n.max_value(),
+        ] {
+            assert_eq!(pow_mod_ct(a, e, n), a.pow_mod(e, n));
+        }
+        assert_eq!(pow_mod_ct(uz64(5), uz64(117), uz64(19)), uz64(1));
+        assert_eq!(pow_mod_ct(uz64(4), uz64(13), uz64(497)), uz64(445));
+        assert_eq!(pow_mod_ct(uz64(4), uz64(13), uz64(1)), uz64(0));
+        let b = n.max_value();
+        assert_eq!(
+            pow_mod_ct(b, uz256(&[3, 0, 0, 0, 0, 0, 0, 0]), n),
+            b.pow_mod(uz256(&[3, 0, 0, 0, 0, 0, 0, 0]), n)
+        );
+    }
+
+    #[test]
+    fn roundtrip_ct0() {
+        let m = Montgomery::new(uz64(1_000_000_007)).unwrap();
+        for &v in &[0, 1, 2, 999_999_999, 1_000_000_006, 1_000_000_008, u64::MAX] {
+            let x = m.to_mont_ct(uz64(v));
+            assert_eq!(x, m.to_mont(uz64(v)));
+            assert_eq!(m.from_mont_ct(x), uz64(v % 1_000_000_007));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "pow_mod_ct requires an odd modulus")]
+    fn pow_mod_ct1() {
+        pow_mod_ct(uz64(4), uz64(13), uz64(10));
+    }
+}
@@ -0,0 +1,337 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Number theoretic transform multiplication on limb slices.  The
+// convolution is done modulo three word sized primes and recombined with
+// the Chinese remainder theorem.  The smallest prime has roots of unity of
+// order up to 2^26, which bounds the transform length.
+
+use crate::limbs;
+use crate::toom3;
+
+// Limb count from which mulc hands over to mul_ntt: at 512 limbs the two
+// are level, from 1024 up the transform wins.
+pub const THRESHOLD: usize = 1024;
+
+// (prime, primitive root), each prime of the form c * 2^k + 1 with k >= 26.
+const PRIMES: [(u32, u32); 3] = [(3_221_225_473, 5), (2_013_265_921, 31), (469_762_049, 3)];
+
+fn pow_mod(mut b: u64, mut e: u64, p: u64) -> u64 {
+    let mut r = 1;
+    while e != 0 {
+        if e & 1 != 0 {
+            r = r * b % p;
+        }
+        b = b * b % p;
+        e >>= 1;
+    }
+    r
+}
+
+// Arithmetic modulo one prime p, with multiplications in Montgomery form
+// (R = 2^32) so that the transforms never divide.
+#[derive(Clone, Copy)]
+struct Field {
+    p: u32,
+    // p^-1 mod 2^32
+    pinv: u32,
+    // R^2 mod p
+    r2: u32,
+}
+
+impl Field {
+    fn new(p: u32) -> Self {
+        let mut x: u32 = 1;
+        for _ in 0..5 {
+            x = x.wrapping_mul(2u32.wrapping_sub(p.wrapping_mul(x)));
+        }
+        let r = (1u64 << 32) % p as u64;
+        Field {
+            p,
+            pinv: x,
+            r2: (r * r % p as u64) as u32,
+        }
+    }
+
+    // a b R^-1 mod p, for a < p.
+    fn mul(self, a: u32, b: u32) -> u32 {
+        let t = a as u64 * b as u64;
+        let m = (t as u32).wrapping_mul(self.pinv);
+        let mp = m as u64 * self.p as u64;
+        // The low words of t and m p agree, so only the high words differ.
+        let (r, borrow) = ((t >> 32) as u32).overflowing_sub((mp >> 32) as u32);
+        if borrow {
+            r.wrapping_add(self.p)
+        } else {
+            r
+        }
+    }
+
+    fn to_mont(self, a: u32) -> u32 {
+        self.mul(a, self.r2)
+    }
+
+    fn add(self, a: u32, b: u32) -> u32 {
+        let (s, c) = a.overflowing_add(b);
+        if c || s >= self.p {
+            s.wrapping_sub(self.p)
+        } else {
+            s
+        }
+    }
+
+    fn sub(self, a: u32, b: u32) -> u32 {
+        let (d, borrow) = a.overflowing_sub(b);
+        if borrow {
+            d.wrapping_add(self.p)
+        } else {
+            d
+        }
+    }
+}
+
+// The powers w^0 .. w^(len/2 - 1) of a root of unity w of order len, in
+// Montgomery form, at roots[len / 2 .. len] for each len = 2, 4, .., n.
+fn roots(n: usize, f: Field, g: u32) -> Vec<u32> {
+    let mut roots = vec![0; n];
+    let w = pow_mod(g as u64, (f.p as u64 - 1) / n as u64, f.p as u64) as u32;
+    let w = f.to_mont(w);
+    let h = n / 2;
+    roots[h] = f.to_mont(1);
+    for k in h + 1..n {
+        roots[k] = f.mul(roots[k - 1], w);
+    }
+    for k in (1..h).rev() {
+        roots[k] = roots[2 * k];
+    }
+    roots
+}
+
+// In place forward transform; the inverse is this followed by reversing
+// a[1..] and scaling by 1/n.
+fn transform(a: &mut [u32], roots: &[u32], f: Field) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let w = &roots[len / 2..len];
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for ((x, y), &wk) in lo.iter_mut().zip(hi.iter_mut()).zip(w) {
+                let u = *x;
+                let v = f.mul(*y, wk);
+                *x = f.add(u, v);
+                *y = f.sub(u, v);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+fn forward(a: &[u32], n: usize, roots: &[u32], f: Field) -> Vec<u32> {
+    let mut fa: Vec<u32> = a.iter().map(|&v| v % f.p).collect();
+    fa.resize(n, 0);
+    transform(&mut fa, roots, f);
+    fa
+}
+
+// Undoes the transform of a pointwise product of transforms, whose
+// Montgomery multiplications left a factor R^-1 on every element.
+fn inverse(fa: &mut [u32], roots: &[u32], f: Field) {
+    let n = fa.len();
+    transform(fa, roots, f);
+    fa[1..].reverse();
+    let p = f.p as u64;
+    let scale = pow_mod(n as u64, p - 2, p) * f.r2 as u64 % p;
+    for x in fa.iter_mut() {
+        *x = f.mul(*x, scale as u32);
+    }
+}
+
+fn convolve(a: &[u32], b: &[u32], n: usize, (p, g): (u32, u32)) -> Vec<u32> {
+    let f = Field::new(p);
+    let roots = roots(n, f, g);
+    let mut fa = forward(a, n, &roots, f);
+    let fb = forward(b, n, &roots, f);
+    for (x, &y) in fa.iter_mut().zip(fb.iter()) {
+        *x = f.mul(*x, y);
+    }
+    inverse(&mut fa, &roots, f);
+    fa
+}
+
+fn autoconvolve(a: &[u32], n: usize, (p, g): (u32, u32)) -> Vec<u32> {
+    let f = Field::new(p);
+    let roots = roots(n, f, g);
+    let mut fa = forward(a, n, &roots, f);
+    for x in fa.iter_mut() {
+        *x = f.mul(*x, *x);
+    }
+    inverse(&mut fa, &roots, f);
+    fa
+}
+
+fn recombine(c: [Vec<u32>; 3], r: &mut [u32]) {
+    let [p1, p2, p3] = PRIMES.map(|(p, _)| p as u64);
+    let p1_inv = pow_mod(p1 % p2, p2 - 2, p2);
+    let p12_inv = pow_mod(p1 * p2 % p3, p3 - 2, p3);
+    let mut carry = 0u128;
+    for (i, v) in r.iter_mut().enumerate() {
+        let (r1, r2, r3) = (c[0][i] as u64, c[1][i] as u64, c[2][i] as u64);
+        let x12 = r1 + p1 * ((r2 + p2 - r1 % p2) % p2 * p1_inv % p2);
+        let t = (r3 + p3 - x12 % p3) % p3 * p12_inv % p3;
+        carry += x12 as u128 + (p1 * p2) as u128 * t as u128;
+        *v = carry as u32;
+        carry >>= 32;
+    }
+    debug_assert_eq!(carry, 0);
+}
+
+fn product(a: &[u32], b: &[u32], r: &mut [u32]) {
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+    let n = (a.len() + b.len()).next_power_of_two();
+    assert!(n <= 1 << 26);
+    let c = [
+        convolve(a, b, n, PRIMES[0]),
+        convolve(a, b, n, PRIMES[1]),
+        convolve(a, b, n, PRIMES[2]),
+    ];
+    recombine(c, &mut r[..a.len() + b.len()]);
+}
+
+fn square(a: &[u32], r: &mut [u32]) {
+    if a.is_empty() {
+        return;
+    }
+    let n = (2 * a.len()).next_power_of_two();
+    assert!(n <= 1 << 26);
+    let c = [
+        autoconvolve(a, n, PRIMES[0]),
+        autoconvolve(a, n, PRIMES[1]),
+        autoconvolve(a, n, PRIMES[2]),
+    ];
+    recombine(c, &mut r[..2 * a.len()]);
+}
+
+// Operands whose significant limbs fall below the Toom-3 threshold are
+// handed down, so that small values in wide types stay cheap.
+pub fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (at, bt) = (limbs::trimmed(a), limbs::trimmed(b));
+    if at.len().min(bt.len()) < toom3::THRESHOLD {
+        return toom3::mul(a, b);
+    }
+    let mut r = vec![0; a.len() + b.len()];
+    product(at, bt, &mut r);
+    r
+}
+
+pub fn sqr(a: &[u32]) -> Vec<u32> {
+    let at = limbs::trimmed(a);
+    if at.len() < toom3::THRESHOLD {
+        return toom3::sqr(a);
+    }
+    let mut r = vec![0; 2 * a.len()];
+    square(at, &mut r);
+    r
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn pseudo_random(n: usize, seed: u32) -> Vec<u32> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                x = x.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                x
+            })
+            .collect()
+    }
+
+    fn ntt_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut r = vec![0; a.len() + b.len()];
+        product(a, b, &mut r);
+        r
+    }
+
+    fn ntt_sqr(a: &[u32]) -> Vec<u32> {
+        let mut r = vec![0; 2 * a.len()];
+        square(a, &mut r);
+        r
+    }
+
+    #[test]
+    fn mul0() {
+        for &n in &[1, 2, 3, 17, 64, 100, 513] {
+            let a = pseudo_random(n, n as u32);
+            let b = pseudo_random(n, !(n as u32));
+            assert_eq!(ntt_mul(&a, &b), limbs::mul(&a, &b));
+        }
+    }
+
+    #[test]
+    fn mul1() {
+        let a = vec![u32::MAX; 1024];
+        assert_eq!(mul(&a, &a), limbs::mul(&a, &a));
+    }
+
+    #[test]
+    fn mul2() {
+        let a = pseudo_random(300, 7);
+        let b = pseudo_random(45, 11);
+        assert_eq!(ntt_mul(&a, &b), limbs::mul(&a, &b));
+        assert_eq!(ntt_mul(&b, &a), limbs::mul(&b, &a));
+    }
+
+    #[test]
+    fn sqr0() {
+        for &n in &[1, 5, 64, 257] {
+            let a = pseudo_random(n, n as u32);
+            assert_eq!(ntt_sqr(&a), limbs::mul(&a, &a));
+        }
+        let a = vec![u32::MAX; 1024];
+        assert_eq!(sqr(&a), limbs::mul(&a, &a));
+    }
+
+    #[test]
+    fn mul3() {
+        assert_eq!(ntt_mul(&[], &[1, 2]), vec![0, 0]);
+        assert_eq!(mul(&[0, 0], &[5, 6]), vec![0; 4]);
+        let mut a = vec![0; 2048];
+        a[0] = 3;
+        let b = pseudo_random(2048, 5);
+        assert_eq!(mul(&a, &b), limbs::mul(&a, &b));
+    }
+}
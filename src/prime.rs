@@ -0,0 +1,387 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Primality testing: trial division by small primes, Miller-Rabin and
+// Baillie-PSW (Miller-Rabin base 2 plus a strong Lucas test with
+// Selfridge's parameters), and prime stepping built on them.
+
+use crate::Uintz;
+
+// Primes below 211, tried as divisors before any modular exponentiation.
+pub const SMALL_PRIMES: [u32; 46] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199,
+];
+
+// Miller-Rabin bases that are decisive below 2^64.
+const WITNESSES_64: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+enum Trial {
+    Prime,
+    Composite,
+    Unknown,
+}
+
+fn trial_division<T: Uintz>(n: T) -> Trial {
+    if n.bits() <= 32 && n.lo32() < 2 {
+        return Trial::Composite;
+    }
+    for &p in SMALL_PRIMES.iter() {
+        if n.divr32(p).1 == 0 {
+            return if n.bits() <= 32 && n.lo32() == p {
+                Trial::Prime
+            } else {
+                Trial::Composite
+            };
+        }
+    }
+    if n.bits() <= 32 && n.lo32() < 211 * 211 {
+        Trial::Prime
+    } else {
+        Trial::Unknown
+    }
+}
+
+// Strong probable prime test to base a, for odd n > 3 and 1 < a < n - 1.
+fn miller_rabin<T: Uintz>(n: T, a: T) -> bool {
+    let one = n.zero().addc32(1, false).0;
+    let m = n.subb32(1, false).0;
+    let s = m.trailing_zeros();
+    let mut x = a.pow_mod(m.shr(s), n);
+    if x == one || x == m {
+        return true;
+    }
+    for _ in 1..s {
+        x = x.mul_mod(x, n);
+        if x == m {
+            return true;
+        }
+    }
+    false
+}
+
+// Jacobi symbol (d/n) for odd n > |d| and odd d, through reciprocity
+// against the single-word residue n mod |d|.
+fn jacobi_small<T: Uintz>(d: i32, n: T) -> i32 {
+    let n4 = n.lo32() & 3;
+    let mut a = n.divr32(d.unsigned_abs()).1;
+    let mut b = d.unsigned_abs();
+    let mut j = if d < 0 && n4 == 3 { -1 } else { 1 };
+    if b & 3 == 3 && n4 == 3 {
+        j = -j;
+    }
+    while a != 0 {
+        while a & 1 == 0 {
+            a >>= 1;
+            if b & 7 == 3 || b & 7 == 5 {
+                j = -j;
+            }
+        }
+        std::mem::swap(&mut a, &mut b);
+        if a & 3 == 3 && b & 3 == 3 {
+            j = -j;
+        }
+        a %= b;
+    }
+    if b == 1 {
+        j
+    } else {
+        0
+    }
+}
+
+// Residue of a small signed value modulo n > |v|.
+fn residue<T: Uintz>(v: i64, n: T) -> T {
+    let a = n.zero().addc32(v.unsigned_abs() as u32, false).0;
+    if v < 0 {
+        n.subb(a, false).0
+    } else {
+        a
+    }
+}
+
+// Strong Lucas probable prime test with P = 1 and D, Q chosen by
+// Selfridge's method A, for odd n with no small factors.
+fn strong_lucas<T: Uintz>(n: T) -> bool {
+    if n.is_perfect_square() {
+        return false;
+    }
+    let mut d: i32 = 5;
+    while jacobi_small(d, n) != -1 {
+        d = if d > 0 { -d - 2 } else { -d + 2 };
+    }
+    let q = residue((1 - d as i64) / 4, n);
+    let dd = residue(d as i64, n);
+    let zero = n.zero();
+    let half_n = n.shr(1).addc32(1, false).0;
+    let half = |x: T| {
+        if x.lo32() & 1 == 0 {
+            x.shr(1)
+        } else {
+            x.shr(1).addc(half_n, false).0
+        }
+    };
+    let (m, _) = n.addc32(1, false);
+    let s = m.trailing_zeros();
+    let k = m.shr(s);
+    let one = zero.addc32(1, false).0;
+    let (mut u, mut v, mut qk) = (one, one, q);
+    for i in (0..k.bits() - 1).rev() {
+        u = u.mul_mod(v, n);
+        v = v.mul_mod(v, n).sub_mod(qk.add_mod(qk, n), n);
+        qk = qk.mul_mod(qk, n);
+        if k.shr(i).lo32() & 1 != 0 {
+            let u2 = half(u.add_mod(v, n));
+            v = half(dd.mul_mod(u, n).add_mod(v, n));
+            u = u2;
+            qk = qk.mul_mod(q, n);
+        }
+    }
+    if u == zero || v == zero {
+        return true;
+    }
+    for _ in 1..s {
+        v = v.mul_mod(v, n).sub_mod(qk.add_mod(qk, n), n);
+        if v == zero {
+            return true;
+        }
+        qk = qk.mul_mod(qk, n);
+    }
+    false
+}
+
+pub trait Primality: Uintz {
+    // Miller-Rabin with base 2 and rounds further bases derived from n;
+    // exact for values below 2^64.  The bases are a public function of n,
+    // so composites can be built to pass: only use this on input not
+    // chosen by an adversary, and is_prime_bpsw otherwise.
+    fn is_probable_prime(self, rounds: u32) -> bool {
+        match trial_division(self) {
+            Trial::Prime => return true,
+            Trial::Composite => return false,
+            Trial::Unknown => {}
+        }
+        let small = |b: u32| self.zero().addc32(b, false).0;
+        if self.bits() <= 64 {
+            return WITNESSES_64.iter().all(|&b| miller_rabin(self, small(b)));
+        }
+        if !miller_rabin(self, small(2)) {
+            return false;
+        }
+        // Bases in [2, n - 2] from a xorshift stream seeded by n.
+        let mut x = self.lo32() | 1;
+        let mut limbs = vec![0; self.limbs()];
+        let range = self.subb32(3, false).0;
+        for _ in 0..rounds {
+            for l in limbs.iter_mut() {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                *l = x;
+            }
+            let a = self.read_limbs(&limbs).divr(range).1.addc32(2, false).0;
+            if !miller_rabin(self, a) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn is_prime_bpsw(self) -> bool {
+        match trial_division(self) {
+            Trial::Prime => true,
+            Trial::Composite => false,
+            Trial::Unknown => {
+                miller_rabin(self, self.zero().addc32(2, false).0) && strong_lucas(self)
+            }
+        }
+    }
+
+    fn next_prime(self) -> Option<Self> {
+        if self.bits() <= 1 {
+            return Some(self.zero().addc32(2, false).0);
+        }
+        let mut n = self.addc32(1 + (self.lo32() & 1), false);
+        if self.bits() <= 32 && self.lo32() == 2 {
+            n = self.addc32(1, false);
+        }
+        while !n.1 {
+            if n.0.is_prime_bpsw() {
+                return Some(n.0);
+            }
+            n = n.0.addc32(2, false);
+        }
+        None
+    }
+
+    fn prev_prime(self) -> Option<Self> {
+        if self.bits() <= 32 && self.lo32() <= 3 {
+            return if self.lo32() == 3 {
+                Some(self.zero().addc32(2, false).0)
+            } else {
+                None
+            };
+        }
+        let mut n = self.subb32(1 + (self.lo32() & 1), false).0;
+        loop {
+            if n.is_prime_bpsw() {
+                return Some(n);
+            }
+            n = n.subb32(2, false).0;
+        }
+    }
+}
+
+impl<T: Uintz> Primality for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Primality, SMALL_PRIMES};
+    use crate::{from_u32, Uintz, Uz, Uz32};
+
+    fn uz64(v: u64) -> Uz<Uz32> {
+        from_u32(0)
+            .augment()
+            .read_limbs(&[v as u32, (v >> 32) as u32])
+    }
+
+    fn uz128(v: &[u32]) -> Uz<Uz<Uz32>> {
+        from_u32(0).augment().augment().read_limbs(v)
+    }
+
+    fn uz256(v: &[u32]) -> Uz<Uz<Uz<Uz32>>> {
+        from_u32(0).augment().augment().augment().read_limbs(v)
+    }
+
+    #[test]
+    fn small0() {
+        let mut sieve = vec![true; 5000];
+        sieve[0] = false;
+        sieve[1] = false;
+        for i in 2..5000 {
+            if sieve[i] {
+                for j in (i * i..5000).step_by(i) {
+                    sieve[j] = false;
+                }
+            }
+        }
+        assert_eq!(
+            SMALL_PRIMES.to_vec(),
+            (0..211)
+                .filter(|&i| sieve[i as usize])
+                .collect::<Vec<u32>>()
+        );
+        for (i, &p) in sieve.iter().enumerate() {
+            assert_eq!(from_u32(i as u32).is_probable_prime(0), p);
+            assert_eq!(from_u32(i as u32).is_prime_bpsw(), p);
+            assert_eq!(uz64(i as u64).is_prime_bpsw(), p);
+        }
+    }
+
+    #[test]
+    fn pseudoprimes0() {
+        // Strong pseudoprimes to base 2, Carmichael numbers, and a strong
+        // pseudoprime to bases 2, 3, 5 and 7.
+        for &n in &[
+            2047u64, 3277, 4033, 4681, 8321, 561, 1105, 41041, 3215031751,
+        ] {
+            assert!(!uz64(n).is_probable_prime(0));
+            assert!(!uz64(n).is_prime_bpsw());
+        }
+        // Strong pseudoprime to every prime base below 41.
+        let n = uz128(&[0x2410_a5fd, 0x51ad_c5b2, 0x0002_be69, 0]);
+        assert!(!n.is_prime_bpsw());
+        assert!(!n.is_probable_prime(10));
+        assert!(!uz64(3825123056546413051).is_probable_prime(0));
+    }
+
+    #[test]
+    fn primes0() {
+        for &n in &[
+            4294967291u64,
+            4294967311,
+            (1 << 61) - 1,
+            0xffff_ffff_ffff_ffc5,
+            1_000_000_000_000_000_003,
+        ] {
+            assert!(uz64(n).is_probable_prime(0));
+            assert!(uz64(n).is_prime_bpsw());
+        }
+        assert!(!uz64(4294967297).is_prime_bpsw());
+        assert!(!uz64(((1u64 << 31) - 1) * 4294967291).is_probable_prime(0));
+        let m127 = uz128(&[u32::MAX, u32::MAX, u32::MAX, 0x7fff_ffff]);
+        assert!(m127.is_probable_prime(5));
+        assert!(m127.is_prime_bpsw());
+        let m61 = uz128(&[u32::MAX, 0x1fff_ffff, 0, 0]);
+        let c = m61
+            .mulc(uz128(&[0xffff_ffc5, u32::MAX, 0, 0]), m61.zero())
+            .0;
+        assert!(!c.is_probable_prime(5));
+        assert!(!c.is_prime_bpsw());
+        let p = uz256(&[
+            u32::MAX - 18,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            0x7fff_ffff,
+        ]);
+        assert!(p.is_probable_prime(5));
+        assert!(p.is_prime_bpsw());
+        assert!(!p.subb32(2, false).0.is_prime_bpsw());
+    }
+
+    #[test]
+    fn next_prime0() {
+        assert_eq!(from_u32(0).next_prime(), Some(from_u32(2)));
+        assert_eq!(from_u32(1).next_prime(), Some(from_u32(2)));
+        assert_eq!(from_u32(2).next_prime(), Some(from_u32(3)));
+        assert_eq!(from_u32(3).next_prime(), Some(from_u32(5)));
+        assert_eq!(from_u32(24).next_prime(), Some(from_u32(29)));
+        assert_eq!(from_u32(4294967291).next_prime(), None);
+        assert_eq!(uz64(1 << 32).next_prime(), Some(uz64(4294967311)));
+        let p64 = uz128(&[0, 0, 1, 0]);
+        assert_eq!(p64.next_prime(), Some(uz128(&[13, 0, 1, 0])));
+        let p128 = uz256(&[0, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(p128.next_prime(), Some(uz256(&[51, 0, 0, 0, 1, 0, 0, 0])));
+    }
+
+    #[test]
+    fn prev_prime0() {
+        assert_eq!(from_u32(2).prev_prime(), None);
+        assert_eq!(from_u32(3).prev_prime(), Some(from_u32(2)));
+        assert_eq!(from_u32(4).prev_prime(), Some(from_u32(3)));
+        assert_eq!(from_u32(30).prev_prime(), Some(from_u32(29)));
+        assert_eq!(
+            from_u32(0).max_value().prev_prime(),
+            Some(from_u32(4294967291))
+        );
+        assert_eq!(
+            uz128(&[0, 0, 1, 0]).prev_prime(),
+            Some(uz128(&[0xffff_ffc5, u32::MAX, 0, 0]))
+        );
+        let p128 = uz256(&[0, 0, 0, 0, 1, 0, 0, 0]);
+        let q = uz256(&[u32::MAX - 158, u32::MAX, u32::MAX, u32::MAX, 0, 0, 0, 0]);
+        assert_eq!(p128.prev_prime(), Some(q));
+    }
+}
@@ -0,0 +1,318 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Deterministic generators with wide state: a PCG with 128-bit LCG state
+// and XSL-RR output (pcg64), and xoshiro256** over 256 bits. Both can
+// jump ahead by any distance given as a Uz, for parallel streams.
+
+use crate::gf2n::Gf2n;
+use crate::random::{Rng, SplitMix64};
+use crate::{from_u32, Uintz, Uz, Uz32};
+
+type U64 = Uz<Uz32>;
+type U128 = Uz<U64>;
+type U256 = Uz<U128>;
+
+// PCG's 128-bit multiplier.
+const PCG_MULT: [u32; 4] = [0x9fcc_f645, 0x4385_df64, 0x1fc6_5da4, 0x2360_ed05];
+
+// The characteristic polynomial of the xoshiro256 linear engine, without
+// its x^256 term, as found by Berlekamp-Massey on the output of one bit
+// of state.
+const XOSHIRO_POLY: [u32; 8] = [
+    0xb0f0_f001,
+    0x9d11_6f2b,
+    0xcefd_1a5e,
+    0x0280_002b,
+    0x2625_9f85,
+    0x04b4_edcf,
+    0x3f3e_cb19,
+    0x0003_c03c,
+];
+
+// x^(2^128) modulo XOSHIRO_POLY, the reference jump polynomial.
+const XOSHIRO_JUMP: [u32; 8] = [
+    0x3cfd_0aba,
+    0x180e_c6d3,
+    0xf0c9_392c,
+    0xd5a6_1266,
+    0xe03f_c9aa,
+    0xa958_2618,
+    0x29b1_661c,
+    0x39ab_dc45,
+];
+
+// Zero-extended to the full width.
+fn u128_from(limbs: &[u32]) -> U128 {
+    let mut l = [0; 4];
+    l[..limbs.len()].copy_from_slice(limbs);
+    from_u32(0).augment().augment().read_limbs(&l)
+}
+
+fn u256_from(limbs: &[u32]) -> U256 {
+    let mut l = [0; 8];
+    l[..limbs.len()].copy_from_slice(limbs);
+    from_u32(0).augment().augment().augment().read_limbs(&l)
+}
+
+// The shifted parts do not overlap, so xor joins them.
+fn rotl(x: U64, k: u32) -> U64 {
+    x.shl(k).xor(x.shr(64 - k))
+}
+
+fn limbs_from(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pcg64 {
+    state: U128,
+    inc: U128,
+}
+
+impl Pcg64 {
+    pub fn new(state: U128, stream: U128) -> Self {
+        Self::from_state_inc(state, stream.shl(1).addc32(1, false).0)
+    }
+
+    // The first 16 bytes are the state and the last 16 the increment,
+    // both little endian; the increment is forced odd.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let l = limbs_from(&seed);
+        let inc = u128_from(&l[4..]);
+        let inc = if inc.lo32() & 1 == 0 {
+            inc.addc32(1, false).0
+        } else {
+            inc
+        };
+        Self::from_state_inc(u128_from(&l[..4]), inc)
+    }
+
+    fn from_state_inc(state: U128, inc: U128) -> Self {
+        let mut g = Pcg64 {
+            state: state.addc(inc, false).0,
+            inc,
+        };
+        g.step();
+        g
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.mulc(u128_from(&PCG_MULT), self.inc).0;
+    }
+
+    // Moves the generator delta steps ahead (Brown, "Random number
+    // generation with arbitrary strides").
+    pub fn advance(&mut self, delta: U128) {
+        let one = self.state.zero().addc32(1, false).0;
+        let (mut acc_mult, mut acc_plus) = (one, self.state.zero());
+        let (mut cur_mult, mut cur_plus) = (u128_from(&PCG_MULT), self.inc);
+        for i in 0..delta.bits() {
+            if delta.shr(i).lo32() & 1 == 1 {
+                acc_mult = acc_mult.mul_lo(cur_mult);
+                acc_plus = acc_plus.mulc(cur_mult, cur_plus).0;
+            }
+            cur_plus = cur_mult.addc32(1, false).0.mul_lo(cur_plus);
+            cur_mult = cur_mult.square();
+        }
+        self.state = acc_mult.mulc(self.state, acc_plus).0;
+    }
+}
+
+impl Rng for Pcg64 {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+        let mut l = [0; 4];
+        self.state.write_limbs(&mut l);
+        let x = (l[3] as u64) << 32 | l[2] as u64;
+        let y = (l[1] as u64) << 32 | l[0] as u64;
+        (x ^ y).rotate_right(l[3] >> 26)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Xoshiro256 {
+    s: U256,
+}
+
+impl Xoshiro256 {
+    // The state must not be zero.
+    pub fn new(s: U256) -> Self {
+        assert!(s != s.zero(), "xoshiro256 state must not be zero");
+        Xoshiro256 { s }
+    }
+
+    // Little endian state; an all-zero seed is replaced by SplitMix64
+    // output, as the engine would never leave zero.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let s = u256_from(&limbs_from(&seed));
+        if s != s.zero() {
+            return Xoshiro256 { s };
+        }
+        let mut sm = SplitMix64::new(0);
+        Xoshiro256 { s: sm.random(s) }
+    }
+
+    // The four 64-bit words of the state are the lanes s.lo.lo, s.lo.hi,
+    // s.hi.lo and s.hi.hi.
+    fn step(&mut self) -> u64 {
+        let Uz {
+            hi: Uz { hi: s3, lo: s2 },
+            lo: Uz { hi: s1, lo: s0 },
+        } = self.s;
+        let r = rotl(s1.mul_lo(from_u32(5).augment()), 7).mul_lo(from_u32(9).augment());
+        let t = s1.shl(17);
+        let s2 = s2.xor(s0);
+        let s3 = s3.xor(s1);
+        let s1 = s1.xor(s2);
+        let s0 = s0.xor(s3);
+        let s2 = s2.xor(t);
+        let s3 = rotl(s3, 45);
+        self.s = Uz {
+            hi: Uz { hi: s3, lo: s2 },
+            lo: Uz { hi: s1, lo: s0 },
+        };
+        (r.hi.lo32() as u64) << 32 | r.lo32() as u64
+    }
+
+    // Replaces the state with c(T) applied to it, T being one step of the
+    // engine and c the polynomial whose coefficients are the bits of c.
+    fn apply(&mut self, c: U256) {
+        let mut acc = self.s.zero();
+        for i in 0..256 {
+            if c.shr(i).lo32() & 1 == 1 {
+                acc = acc.xor(self.s);
+            }
+            self.step();
+        }
+        self.s = acc;
+    }
+
+    // 2^128 steps ahead.
+    pub fn jump(&mut self) {
+        self.apply(u256_from(&XOSHIRO_JUMP));
+    }
+
+    // delta steps ahead, through x^delta modulo the characteristic
+    // polynomial.
+    pub fn advance(&mut self, delta: U256) {
+        let x = self.s.zero().addc32(2, false).0;
+        let c = Gf2n::new(x, 256, u256_from(&XOSHIRO_POLY)).pow(delta);
+        self.apply(c.value());
+    }
+}
+
+impl Rng for Xoshiro256 {
+    fn next_u32(&mut self) -> u32 {
+        (self.step() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{u128_from, u256_from, Pcg64, Xoshiro256};
+    use crate::random::Rng;
+    use crate::Uintz;
+
+    #[test]
+    fn pcg64_0() {
+        let mut g = Pcg64::new(u128_from(&[42]), u128_from(&[54]));
+        assert_eq!(g.next_u64(), 0x86b1_da1d_7206_2b68);
+        assert_eq!(g.next_u64(), 0x1304_aa46_c985_3d39);
+        assert_eq!(g.next_u64(), 0xa367_0e9e_0dd5_0358);
+        assert_eq!(g.next_u32(), 0x9a7d_ae00);
+        let mut seed = [0; 32];
+        seed[0] = 42;
+        seed[16] = 109;
+        assert_eq!(
+            Pcg64::from_seed(seed),
+            Pcg64::new(u128_from(&[42]), u128_from(&[54]))
+        );
+    }
+
+    #[test]
+    fn pcg64_advance0() {
+        let g = Pcg64::new(u128_from(&[7, 6, 5, 4]), u128_from(&[3, 2, 1]));
+        for &k in &[0u32, 1, 2, 1000] {
+            let (mut a, mut b) = (g, g);
+            for _ in 0..k {
+                a.next_u64();
+            }
+            b.advance(u128_from(&[k]));
+            assert_eq!(a, b);
+        }
+        let mut b = g;
+        b.advance(g.state.max_value());
+        b.advance(u128_from(&[1]));
+        assert_eq!(b, g);
+    }
+
+    #[test]
+    fn xoshiro256_0() {
+        let mut g = Xoshiro256::new(u256_from(&[1, 0, 2, 0, 3, 0, 4, 0]));
+        assert_eq!(g.next_u64(), 11520);
+        assert_eq!(g.next_u64(), 0);
+        assert_eq!(g.next_u64(), 1509978240);
+        assert_eq!(g.next_u64(), 1215971899390074240);
+        let mut seed = [0; 32];
+        seed[0] = 1;
+        seed[8] = 2;
+        seed[16] = 3;
+        seed[24] = 4;
+        assert_eq!(
+            Xoshiro256::from_seed(seed),
+            Xoshiro256::new(u256_from(&[1, 0, 2, 0, 3, 0, 4, 0]))
+        );
+        let mut z = Xoshiro256::from_seed([0; 32]);
+        assert!(z.next_u64() != 0 || z.next_u64() != 0);
+    }
+
+    #[test]
+    fn xoshiro256_advance0() {
+        let g = Xoshiro256::new(u256_from(&[0xdead_beef, 1, 2, 3, 4, 5, 6, 0xcafe_babe]));
+        for &k in &[0u32, 1, 5, 300] {
+            let (mut a, mut b) = (g, g);
+            for _ in 0..k {
+                a.next_u64();
+            }
+            b.advance(u256_from(&[k]));
+            assert_eq!(a, b);
+        }
+        let (mut a, mut b) = (g, g);
+        a.jump();
+        b.advance(u256_from(&[0, 0, 0, 0, 1]));
+        assert_eq!(a, b);
+        // The period is 2^256 - 1.
+        let mut c = g;
+        c.advance(g.s.max_value());
+        assert_eq!(c, g);
+    }
+}
@@ -0,0 +1,248 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Jacobi symbol and square roots modulo an odd prime, by Tonelli-Shanks
+// or, when p - 1 has a large power of two, by Cipolla's method.
+
+use crate::Uintz;
+
+// Jacobi symbol (a/n) for odd n: 0, 1 or -1.
+pub fn jacobi<T: Uintz>(a: T, n: T) -> i32 {
+    assert!(n.lo32() & 1 == 1, "jacobi symbol of an even modulus");
+    let zero = n.zero();
+    let (mut a, mut n) = (a.divr(n).1, n);
+    let mut j = 1;
+    while a != zero {
+        let tz = a.trailing_zeros();
+        a = a.shr(tz);
+        if tz & 1 == 1 && (n.lo32() & 7 == 3 || n.lo32() & 7 == 5) {
+            j = -j;
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a.lo32() & 3 == 3 && n.lo32() & 3 == 3 {
+            j = -j;
+        }
+        a = a.divr(n).1;
+    }
+    if n == zero.addc32(1, false).0 {
+        j
+    } else {
+        0
+    }
+}
+
+// Some x with x^2 = a (mod p), the smaller of the two roots, or None if
+// a is a non-residue. The modulus must be an odd prime.
+pub fn sqrt_mod<T: Uintz>(a: T, p: T) -> Option<T> {
+    let a = a.divr(p).1;
+    if a == p.zero() {
+        return Some(a);
+    }
+    if jacobi(a, p) != 1 {
+        return None;
+    }
+    let s = p.subb32(1, false).0.trailing_zeros();
+    let r = if s == 1 {
+        a.pow_mod(p.shr(2).addc32(1, false).0, p)
+    } else if s * (s - 1) > 8 * p.bits() + 20 {
+        cipolla(a, p)
+    } else {
+        tonelli_shanks(a, p)
+    };
+    let q = p.subb(r, false).0;
+    Some(if q < r { q } else { r })
+}
+
+fn small_non_residue<T: Uintz>(p: T) -> T {
+    let mut z = p.zero().addc32(2, false).0;
+    while jacobi(z, p) != -1 {
+        z = z.addc32(1, false).0;
+    }
+    z
+}
+
+fn tonelli_shanks<T: Uintz>(a: T, p: T) -> T {
+    let one = p.zero().addc32(1, false).0;
+    let p1 = p.subb32(1, false).0;
+    let mut m = p1.trailing_zeros();
+    let q = p1.shr(m);
+    let mut c = small_non_residue(p).pow_mod(q, p);
+    let mut t = a.pow_mod(q, p);
+    let mut r = a.pow_mod(q.shr(1).addc32(1, false).0, p);
+    while t != one {
+        let mut i = 1;
+        let mut t2 = t.mul_mod(t, p);
+        while t2 != one {
+            t2 = t2.mul_mod(t2, p);
+            i += 1;
+        }
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = b.mul_mod(b, p);
+        }
+        m = i;
+        c = b.mul_mod(b, p);
+        t = t.mul_mod(c, p);
+        r = r.mul_mod(b, p);
+    }
+    r
+}
+
+fn cipolla<T: Uintz>(a: T, p: T) -> T {
+    let zero = p.zero();
+    let one = zero.addc32(1, false).0;
+    // Find t with t^2 - a a non-residue; then (t + sqrt(w))^((p + 1) / 2)
+    // in GF(p^2) = GF(p)[sqrt(w)] is a root of a.
+    let mut t = one;
+    let w = loop {
+        let w = t.mul_mod(t, p).sub_mod(a, p);
+        if jacobi(w, p) == -1 {
+            break w;
+        }
+        t = t.addc32(1, false).0;
+    };
+    let mul = |(x1, y1): (T, T), (x2, y2): (T, T)| {
+        (
+            x1.mul_mod(x2, p)
+                .add_mod(y1.mul_mod(y2, p).mul_mod(w, p), p),
+            x1.mul_mod(y2, p).add_mod(x2.mul_mod(y1, p), p),
+        )
+    };
+    let e = p.shr(1).addc32(1, false).0;
+    let mut r = (one, zero);
+    for i in (0..e.bits()).rev() {
+        r = mul(r, r);
+        if e.shr(i).lo32() & 1 == 1 {
+            r = mul(r, (t, one));
+        }
+    }
+    r.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cipolla, jacobi, sqrt_mod, tonelli_shanks};
+    use crate::{from_u32, Uintz, Uz, Uz32};
+
+    fn uz64(v: u64) -> Uz<Uz32> {
+        from_u32(0)
+            .augment()
+            .read_limbs(&[v as u32, (v >> 32) as u32])
+    }
+
+    fn uz256(v: &[u32]) -> Uz<Uz<Uz<Uz32>>> {
+        from_u32(0).augment().augment().augment().read_limbs(v)
+    }
+
+    #[test]
+    fn jacobi0() {
+        // Euler's criterion on primes, multiplicativity on composites.
+        for &p in &[3u32, 5, 7, 11, 13, 97] {
+            for a in 0..2 * p {
+                let e = from_u32(a).pow_mod(from_u32((p - 1) / 2), from_u32(p));
+                let want = if a % p == 0 {
+                    0
+                } else if e == from_u32(1) {
+                    1
+                } else {
+                    -1
+                };
+                assert_eq!(jacobi(from_u32(a), from_u32(p)), want);
+                assert_eq!(jacobi(uz64(a as u64), uz64(p as u64)), want);
+            }
+        }
+        for a in 0..50 {
+            let j = jacobi(from_u32(a), from_u32(3)) * jacobi(from_u32(a), from_u32(5 * 7));
+            assert_eq!(jacobi(from_u32(a), from_u32(105)), j);
+        }
+        assert_eq!(jacobi(from_u32(1), from_u32(1)), 1);
+        assert_eq!(jacobi(from_u32(2), from_u32(9)), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "jacobi symbol of an even modulus")]
+    fn jacobi1() {
+        jacobi(from_u32(3), from_u32(8));
+    }
+
+    #[test]
+    fn sqrt_mod0() {
+        for &p in &[3u32, 5, 13, 17, 97, 193, 257, 65537] {
+            for a in 0..p.min(300) {
+                match sqrt_mod(from_u32(a), from_u32(p)) {
+                    Some(r) => {
+                        assert_eq!(r.mul_mod(r, from_u32(p)), from_u32(a));
+                        assert!(r.lo32() <= p / 2);
+                    }
+                    None => assert_eq!(jacobi(from_u32(a), from_u32(p)), -1),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_mod1() {
+        // p = 3 mod 4, Tonelli-Shanks with s = 2 and 3, and Cipolla with s = 30.
+        for &p in &[
+            (1u64 << 61) - 1,
+            0xffff_ffff_ffff_ffc5,
+            1_000_000_009,
+            3221225473,
+        ] {
+            let p = uz64(p);
+            let mut x = uz64(0x0123_4567_89ab_cdef);
+            for _ in 0..20 {
+                let a = x.mul_mod(x, p);
+                let r = sqrt_mod(a, p).unwrap();
+                assert!(r == x.divr(p).1 || r == p.subb(x.divr(p).1, false).0);
+                x = x.mul_lo(x).addc32(0x9e37_79b9, false).0;
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_mod2() {
+        // 2^255 - 19 has p - 1 divisible by 4 only.
+        let p = uz256(&[
+            u32::MAX - 18,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            0x7fff_ffff,
+        ]);
+        let x = uz256(&[9, 0, 0, 0, 0, 0, 0, 0x1234_5678]);
+        let r = sqrt_mod(x.mul_mod(x, p), p).unwrap();
+        assert_eq!(r.mul_mod(r, p), x.mul_mod(x, p));
+        assert_eq!(sqrt_mod(from_u32(2), from_u32(13)), None);
+    }
+
+    #[test]
+    fn methods0() {
+        let p = uz64(3221225473);
+        for &a in &[4u64, 9, 123456789 * 123456789 % 3221225473] {
+            let (t, c) = (tonelli_shanks(uz64(a), p), cipolla(uz64(a), p));
+            assert_eq!(t.mul_mod(t, p), uz64(a));
+            assert_eq!(c.mul_mod(c, p), uz64(a));
+        }
+    }
+}
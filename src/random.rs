@@ -0,0 +1,233 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Random values of any depth from a source of 32-bit words, plus a
+// seeded SplitMix64 source for reproducible tests and fuzzing.
+
+use crate::prime::Primality;
+use crate::Uintz;
+
+pub trait Rng {
+    fn next_u32(&mut self) -> u32;
+
+    fn next_u64(&mut self) -> u64 {
+        let lo = self.next_u32() as u64;
+        (self.next_u32() as u64) << 32 | lo
+    }
+
+    // Uniform over the whole width of proto's type.
+    fn random<T: Uintz>(&mut self, proto: T) -> T
+    where
+        Self: Sized,
+    {
+        let mut limbs = vec![0; proto.limbs()];
+        for l in limbs.iter_mut() {
+            *l = self.next_u32();
+        }
+        proto.read_limbs(&limbs)
+    }
+
+    // Uniform below 2^n, for n up to the width of proto's type.
+    fn random_bits<T: Uintz>(&mut self, proto: T, n: u32) -> T
+    where
+        Self: Sized,
+    {
+        let width = proto.limbs() as u32 * 32;
+        assert!(n <= width, "random_bits wider than the type");
+        self.random(proto).shr(width - n)
+    }
+
+    // Uniform below bound, rejecting draws of bound's bit length that
+    // fall outside it.
+    fn random_below<T: Uintz>(&mut self, bound: T) -> T
+    where
+        Self: Sized,
+    {
+        assert!(bound != bound.zero(), "random_below with a zero bound");
+        let k = bound.bits();
+        loop {
+            let x = self.random_bits(bound, k);
+            if x < bound {
+                return x;
+            }
+        }
+    }
+
+    // Uniform in [lo, hi).
+    fn random_range<T: Uintz>(&mut self, lo: T, hi: T) -> T
+    where
+        Self: Sized,
+    {
+        assert!(lo < hi, "random_range with an empty range");
+        lo.addc(self.random_below(hi.subb(lo, false).0), false).0
+    }
+
+    // A prime of exactly the given bit length, which must be at least 2.
+    // Candidates are forced odd, so 2 bits is drawn apart from them.
+    fn random_prime<T: Uintz>(&mut self, proto: T, bits: u32) -> T
+    where
+        Self: Sized,
+    {
+        assert!(bits >= 2, "no primes below 2 bits");
+        assert!(
+            bits <= proto.limbs() as u32 * 32,
+            "random_prime wider than the type"
+        );
+        if bits == 2 {
+            return proto.zero().addc32(2 | self.next_u32() & 1, false).0;
+        }
+        let one = proto.zero().addc32(1, false).0;
+        let top = one.shl(bits - 1);
+        loop {
+            let x = self.random_bits(proto, bits - 1).addc(top, false).0;
+            let x = if x.lo32() & 1 == 0 {
+                x.addc32(1, false).0
+            } else {
+                x
+            };
+            if x.is_prime_bpsw() {
+                return x;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn step(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let z = self.state;
+        let z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.step() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rng, SplitMix64};
+    use crate::prime::Primality;
+    use crate::{from_u32, Uintz};
+
+    #[test]
+    fn split_mix64_0() {
+        let mut r = SplitMix64::new(0);
+        assert_eq!(r.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(r.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+        assert_eq!(r.next_u32(), 0x06c4_5d18);
+        let (mut a, mut b) = (SplitMix64::new(42), SplitMix64::new(42));
+        let zero = from_u32(0).augment().augment();
+        assert_eq!(a.random(zero), b.random(zero));
+    }
+
+    #[test]
+    fn random_bits0() {
+        let mut r = SplitMix64::new(1);
+        let zero = from_u32(0).augment().augment().augment();
+        let mut top = false;
+        for _ in 0..64 {
+            let x = r.random_bits(zero, 100);
+            assert!(x.bits() <= 100);
+            top |= x.bits() == 100;
+        }
+        assert!(top);
+        assert_eq!(r.random_bits(zero, 0), zero);
+        assert!(r.random_bits(zero, 256).bits() <= 256);
+    }
+
+    #[test]
+    #[should_panic(expected = "random_bits wider than the type")]
+    fn random_bits1() {
+        SplitMix64::new(0).random_bits(from_u32(0).augment(), 65);
+    }
+
+    #[test]
+    fn random_below0() {
+        let mut r = SplitMix64::new(2);
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            let x = r.random_below(from_u32(6));
+            counts[x.lo32() as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| c > 800 && c < 1200));
+        let bound = from_u32(0).augment().max_value().shr(3).addc32(5, false).0;
+        for _ in 0..100 {
+            assert!(r.random_below(bound) < bound);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "random_below with a zero bound")]
+    fn random_below1() {
+        SplitMix64::new(0).random_below(from_u32(0));
+    }
+
+    #[test]
+    fn random_range0() {
+        let mut r = SplitMix64::new(3);
+        let lo = from_u32(0).augment().addc32(1000, false).0;
+        let hi = lo.addc32(10, false).0;
+        for _ in 0..100 {
+            let x = r.random_range(lo, hi);
+            assert!(lo <= x && x < hi);
+        }
+        assert_eq!(r.random_range(lo, lo.addc32(1, false).0), lo);
+    }
+
+    #[test]
+    fn random_prime0() {
+        let mut r = SplitMix64::new(4);
+        let zero = from_u32(0).augment().augment().augment();
+        for &bits in &[2, 3, 17, 64, 128] {
+            let p = r.random_prime(zero, bits);
+            assert_eq!(p.bits(), bits);
+            assert!(p.is_probable_prime(8));
+        }
+        let mut seen = [false; 4];
+        for _ in 0..64 {
+            seen[r.random_prime(zero, 2).lo32() as usize] = true;
+        }
+        assert_eq!(seen, [false, false, true, true]);
+    }
+
+    #[test]
+    #[should_panic(expected = "random_prime wider than the type")]
+    fn random_prime1() {
+        SplitMix64::new(0).random_prime(from_u32(0).augment(), 65);
+    }
+}
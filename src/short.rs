@@ -0,0 +1,194 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Short products on limb slices, in the manner of Mulders: one full
+// product of the leading 70% of the operands plus two short products of
+// what remains.  The high half comes with a guard limb and an exact
+// fallback when the dropped partial products could carry into it.  From
+// the NTT threshold a single full product is taken, since a short one
+// needs the same power of two transform length.
+
+use crate::limbs;
+use crate::ntt;
+use crate::toom3;
+
+// Limb count from which mul_hi and mul_lo hand over to these functions.
+pub const THRESHOLD: usize = 32;
+
+// Below this many limbs the partial products are summed directly.
+const BASECASE: usize = 32;
+
+// The sub-products come in odd sizes, for which Toom-3 already pays off
+// from half the limb count at which mulc starts using it.
+const TOOM3: usize = toom3::THRESHOLD / 2;
+
+fn full(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.len() >= ntt::THRESHOLD {
+        ntt::mul(a, b)
+    } else if a.len() >= TOOM3 {
+        toom3::mul(a, b)
+    } else {
+        limbs::mul(a, b)
+    }
+}
+
+// Limbs of the full product covered by the leading operand limbs.
+fn split(n: usize) -> usize {
+    (7 * n).div_ceil(10)
+}
+
+// Sum of the partial products a_i b_j B^(i+j) with i + j < n, modulo B^n.
+fn lower(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let n = a.len();
+    debug_assert_eq!(n, b.len());
+    if n < BASECASE {
+        let mut r = vec![0; n];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &y) in b[..n - i].iter().enumerate() {
+                let nv = x as u64 * y as u64 + r[i + j] as u64 + carry;
+                r[i + j] = nv as u32;
+                carry = nv >> 32;
+            }
+        }
+        return r;
+    }
+    let k = split(n);
+    let m = n - k;
+    let mut r = full(&a[..k], &b[..k]);
+    r.truncate(n);
+    limbs::add_assign(&mut r[k..], &lower(&a[k..], &b[..m]));
+    limbs::add_assign(&mut r[k..], &lower(&a[..m], &b[k..]));
+    r
+}
+
+// Sum of the partial products a_i b_j B^(i+j) with i + j >= n - 1, plus
+// possibly some below.  Never exceeds a b, and falls short of it by less
+// than (n - 1) B^n.
+fn upper(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let n = a.len();
+    debug_assert_eq!(n, b.len());
+    let mut r = vec![0; 2 * n];
+    if n < BASECASE {
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &y) in b.iter().enumerate().skip(n - 1 - i) {
+                let nv = x as u64 * y as u64 + r[i + j] as u64 + carry;
+                r[i + j] = nv as u32;
+                carry = nv >> 32;
+            }
+            r[i + n] = carry as u32;
+        }
+        return r;
+    }
+    let k = split(n);
+    let m = n - k;
+    r[2 * m..].copy_from_slice(&full(&a[m..], &b[m..]));
+    limbs::add_assign(&mut r[k..], &upper(&a[k..], &b[..m]));
+    limbs::add_assign(&mut r[k..], &upper(&a[..m], &b[k..]));
+    r
+}
+
+// Low n limbs of the product of two n limb operands.
+pub fn mul_lo(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let n = a.len();
+    if n >= ntt::THRESHOLD {
+        let mut r = full(a, b);
+        r.truncate(n);
+        return r;
+    }
+    lower(a, b)
+}
+
+// High n limbs of the 2n limb product of two n limb operands.
+pub fn mul_hi(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let n = a.len();
+    if n >= ntt::THRESHOLD {
+        return full(a, b)[n..].to_vec();
+    }
+    let mut ax = vec![0; n + 1];
+    let mut bx = vec![0; n + 1];
+    ax[1..].copy_from_slice(a);
+    bx[1..].copy_from_slice(b);
+    // With a guard limb below the result, the dropped partial products
+    // (under n B^(n + 1)) can only carry into it when the guard is within
+    // n of overflowing.
+    let u = upper(&ax, &bx);
+    if u[n + 1] as u64 + (n as u64) < 1 << 32 {
+        return u[n + 2..].to_vec();
+    }
+    full(a, b)[n..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn pseudo_random(n: usize, seed: u32) -> Vec<u32> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                x = x.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mul_hi0() {
+        for &n in &[1, 31, 32, 33, 95, 128, 256, 700, 1024] {
+            let a = pseudo_random(n, n as u32);
+            let b = pseudo_random(n, !(n as u32));
+            assert_eq!(mul_hi(&a, &b), limbs::mul(&a, &b)[n..]);
+        }
+    }
+
+    #[test]
+    fn mul_lo0() {
+        for &n in &[1, 31, 32, 33, 95, 128, 256, 700, 1024] {
+            let a = pseudo_random(n, 7 * n as u32);
+            let b = pseudo_random(n, 11 * n as u32);
+            assert_eq!(mul_lo(&a, &b), limbs::mul(&a, &b)[..n]);
+        }
+        let a = vec![u32::MAX; 300];
+        assert_eq!(mul_lo(&a, &a), limbs::mul(&a, &a)[..300]);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = vec![u32::MAX; 300];
+        assert_eq!(mul_hi(&a, &a), limbs::mul(&a, &a)[300..]);
+    }
+
+    #[test]
+    fn upper0() {
+        for &n in &[1, 31, 32, 33, 95, 256, 700] {
+            let a = pseudo_random(n, 3 * n as u32);
+            let b = pseudo_random(n, 5 * n as u32);
+            let p = limbs::mul(&a, &b);
+            let u = upper(&a, &b);
+            assert!(limbs::cmp(&u, &p).is_le());
+            let mut bound = vec![0; 2 * n];
+            bound[n] = n as u32;
+            assert!(limbs::cmp(&limbs::add(&u, &bound), &p).is_gt());
+        }
+    }
+}
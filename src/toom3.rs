@@ -0,0 +1,243 @@
+/*
+ * Copyright 2019 Rafael Lorandi <coolparadox@gmail.com>
+ *
+ * This file is part of uintz, an arbitrary depth unsigned integer
+ * library for the rust language.
+ *
+ * uintz is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * uintz is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with uintz.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+// Toom-Cook 3-way multiplication on limb slices, evaluating at
+// 0, 1, -1, -2 and infinity and interpolating with Bodrato's sequence.
+
+use crate::limbs;
+use std::cmp::Ordering;
+
+// Limb count from which mulc hands over to mul_toom3.
+pub const THRESHOLD: usize = 256;
+
+// Below this many limbs the recursion falls back to schoolbook.
+const BASECASE: usize = 32;
+
+struct Signed {
+    neg: bool,
+    mag: Vec<u32>,
+}
+
+impl Signed {
+    fn new(mag: Vec<u32>) -> Self {
+        Self { neg: false, mag }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        if self.neg == other.neg {
+            return Self {
+                neg: self.neg,
+                mag: limbs::add(&self.mag, &other.mag),
+            };
+        }
+        match limbs::cmp(&self.mag, &other.mag) {
+            Ordering::Less => Self {
+                neg: other.neg,
+                mag: limbs::sub(&other.mag, &self.mag),
+            },
+            _ => Self {
+                neg: self.neg,
+                mag: limbs::sub(&self.mag, &other.mag),
+            },
+        }
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self.add(&Self {
+            neg: !other.neg,
+            mag: other.mag.clone(),
+        })
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self {
+            neg: self.neg != other.neg,
+            mag: product(&self.mag, &other.mag),
+        }
+    }
+
+    fn square(&self) -> Self {
+        Self::new(square(&self.mag))
+    }
+
+    fn double(&self) -> Self {
+        Self {
+            neg: self.neg,
+            mag: limbs::shl1(&self.mag),
+        }
+    }
+
+    fn half(&self) -> Self {
+        Self {
+            neg: self.neg,
+            mag: limbs::shr1(&self.mag),
+        }
+    }
+
+    fn third(&self) -> Self {
+        let mut mag = self.mag.clone();
+        let r = limbs::divr32(&mut mag, 3);
+        debug_assert_eq!(r, 0);
+        Self { neg: self.neg, mag }
+    }
+}
+
+fn split(a: &[u32], k: usize) -> (&[u32], &[u32], &[u32]) {
+    let lo = a.len().min(k);
+    let mid = a.len().min(2 * k);
+    (&a[..lo], &a[lo..mid], &a[mid..])
+}
+
+fn evaluate(m0: &[u32], m1: &[u32], m2: &[u32]) -> [Signed; 5] {
+    let m0 = Signed::new(m0.to_vec());
+    let m2 = Signed::new(m2.to_vec());
+    let t = m0.add(&m2);
+    let p1 = t.add(&Signed::new(m1.to_vec()));
+    let pm1 = t.sub(&Signed::new(m1.to_vec()));
+    let pm2 = pm1.add(&m2).double().sub(&m0);
+    [m0, p1, pm1, pm2, m2]
+}
+
+fn interpolate(k: usize, len: usize, r: [Signed; 5]) -> Vec<u32> {
+    let [r0, r1, rm1, rm2, r4] = r;
+    let r3 = rm2.sub(&r1).third();
+    let r1 = r1.sub(&rm1).half();
+    let r2 = rm1.sub(&r0);
+    let r3 = r2.sub(&r3).half().add(&r4.double());
+    let r2 = r2.add(&r1).sub(&r4);
+    let r1 = r1.sub(&r3);
+    let mut r = vec![0; len];
+    for (i, c) in [r0, r1, r2, r3, r4].iter().enumerate() {
+        let mag = limbs::trimmed(&c.mag);
+        if !mag.is_empty() {
+            debug_assert!(!c.neg);
+            limbs::add_assign(&mut r[i * k..], mag);
+        }
+    }
+    r
+}
+
+fn product(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let a = limbs::trimmed(a);
+    let b = limbs::trimmed(b);
+    if a.len().min(b.len()) < BASECASE {
+        return limbs::mul(a, b);
+    }
+    let k = a.len().max(b.len()).div_ceil(3);
+    let (a0, a1, a2) = split(a, k);
+    let (b0, b1, b2) = split(b, k);
+    let [p0, p1, pm1, pm2, pinf] = evaluate(a0, a1, a2);
+    let [q0, q1, qm1, qm2, qinf] = evaluate(b0, b1, b2);
+    let r = [
+        p0.mul(&q0),
+        p1.mul(&q1),
+        pm1.mul(&qm1),
+        pm2.mul(&qm2),
+        pinf.mul(&qinf),
+    ];
+    interpolate(k, a.len() + b.len(), r)
+}
+
+fn square(a: &[u32]) -> Vec<u32> {
+    let a = limbs::trimmed(a);
+    if a.len() < BASECASE {
+        return limbs::sqr(a);
+    }
+    let k = a.len().div_ceil(3);
+    let (a0, a1, a2) = split(a, k);
+    let [p0, p1, pm1, pm2, pinf] = evaluate(a0, a1, a2);
+    let r = [
+        p0.square(),
+        p1.square(),
+        pm1.square(),
+        pm2.square(),
+        pinf.square(),
+    ];
+    interpolate(k, 2 * a.len(), r)
+}
+
+pub fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut r = product(a, b);
+    r.resize(a.len() + b.len(), 0);
+    r
+}
+
+pub fn sqr(a: &[u32]) -> Vec<u32> {
+    let mut r = square(a);
+    r.resize(2 * a.len(), 0);
+    r
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn pseudo_random(n: usize, seed: u32) -> Vec<u32> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                x = x.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mul0() {
+        for &n in &[1, 31, 32, 33, 95, 96, 100, 256, 700] {
+            let a = pseudo_random(n, n as u32);
+            let b = pseudo_random(n, !(n as u32));
+            assert_eq!(mul(&a, &b), limbs::mul(&a, &b));
+        }
+    }
+
+    #[test]
+    fn mul1() {
+        let a = vec![u32::MAX; 300];
+        assert_eq!(mul(&a, &a), limbs::mul(&a, &a));
+    }
+
+    #[test]
+    fn mul2() {
+        let a = pseudo_random(500, 7);
+        let b = pseudo_random(70, 11);
+        assert_eq!(mul(&a, &b), limbs::mul(&a, &b));
+        assert_eq!(mul(&b, &a), limbs::mul(&b, &a));
+    }
+
+    #[test]
+    fn sqr0() {
+        for &n in &[1, 31, 32, 33, 96, 100, 700] {
+            let a = pseudo_random(n, n as u32);
+            assert_eq!(sqr(&a), limbs::mul(&a, &a));
+        }
+        let a = vec![u32::MAX; 300];
+        assert_eq!(sqr(&a), limbs::mul(&a, &a));
+    }
+
+    #[test]
+    fn mul3() {
+        let mut a = vec![0; 200];
+        a[199] = 1;
+        let b = pseudo_random(200, 3);
+        assert_eq!(mul(&a, &b), limbs::mul(&a, &b));
+    }
+}
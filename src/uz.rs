@@ -18,11 +18,35 @@
  * along with uintz.  If not, see <http://www.gnu.org/licenses/>
  */
 
+use crate::divisor::Divisor32;
+use crate::limbs;
+use crate::ntt;
+use crate::short;
+use crate::toom3;
 use crate::Uintz;
 use crate::Uz;
 use crate::Uz32;
 
 impl Uintz for Uz<Uz32> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
     fn addc(self, other: Self, carry: bool) -> (Self, bool) {
         let (lo, loc) = self.lo.addc(other.lo, carry);
         let (hi, hic) = self.hi.addc(other.hi, loc);
@@ -42,8 +66,290 @@ impl Uintz for Uz<Uz32> {
         }
     }
 
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
     fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
     }
 
     fn max_value(self) -> Self {
@@ -54,6 +360,12 @@ impl Uintz for Uz<Uz32> {
     }
 
     fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
         let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
         let (hl, hlc) = self.hi.mulc(other.lo, llc);
         let (lh, lhc) = self.lo.mulc(other.hi, hl);
@@ -76,6 +388,238 @@ impl Uintz for Uz<Uz32> {
         )
     }
 
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
     fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
         let (lo, lob) = self.lo.subb(other.lo, borrow);
         let (hi, hib) = self.hi.subb(other.hi, lob);
@@ -88,6 +632,54 @@ impl Uintz for Uz<Uz32> {
         (Self { lo, hi }, hib)
     }
 
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
     fn zero(self) -> Self {
         Self {
             hi: self.hi.zero(),
@@ -18,6 +18,7 @@
  * along with uintz.  If not, see <http://www.gnu.org/licenses/>
  */
 
+use crate::divisor::Divisor32;
 use crate::Uintz;
 use crate::Uz;
 use crate::Uz32;
@@ -31,6 +32,27 @@ mod tests {
         from_u32(v)
     }
 
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
+    }
+
     #[test]
     fn eq0() {
         assert_eq!(new(0), new(0));
@@ -74,6 +96,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
     #[test]
     fn addc0() {
         let (v, c) = new(0).addc(new(1), false);
@@ -116,6 +150,367 @@ mod tests {
         assert_eq!(c, true);
     }
 
+    #[test]
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
+    }
+
+    #[test]
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
+    }
+
+    #[test]
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
+    }
+
+    #[test]
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
+    }
+
+    #[test]
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
+    }
+
+    #[test]
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
+    }
+
+    #[test]
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
+    }
+
+    #[test]
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
+    }
+
+    #[test]
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
+    }
+
+    #[test]
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
+
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
+
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
+
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
+    }
+
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
+    }
+
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
+
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+    }
+
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
+
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
+
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
+    }
+
+    #[test]
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
+    }
+
     #[test]
     fn mulc_0() {
         let (v, c) = new(1).mulc(new(1), new(0));
@@ -137,6 +532,101 @@ mod tests {
         assert_eq!(c, new(1));
     }
 
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
     #[test]
     fn mulc32_0() {
         let (v, c) = new(1).mulc32(1, new(0));
@@ -158,6 +648,89 @@ mod tests {
         assert_eq!(c, new(1));
     }
 
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
     #[test]
     fn subb0() {
         let (v, c) = new(1).subb(new(1), false);
@@ -200,9 +773,23 @@ mod tests {
         assert_eq!(c, true);
     }
 
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
+    }
 }
 
 impl Uintz for Uz32 {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let m = modulus.v as u64;
+        Self {
+            v: ((self.v as u64 % m + other.v as u64 % m) % m) as u32,
+        }
+    }
+
     fn addc(self, other: Self, carry: bool) -> (Self, bool) {
         self.addc32(other.v, carry)
     }
@@ -224,8 +811,228 @@ impl Uintz for Uz32 {
         }
     }
 
+    fn bits(self) -> u32 {
+        32 - self.v.leading_zeros()
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        self.v.checked_pow(exp).map(|v| Self { v })
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        if divisor.v == 0 {
+            panic!("attempt to divide by zero");
+        }
+        let d = 32 - divisor.v.leading_zeros();
+        let (mut q, mut r) = (0, self.v);
+        while 32 - r.leading_zeros() >= d {
+            let s = 32 - r.leading_zeros() - d;
+            r ^= divisor.v << s;
+            q |= 1 << s;
+        }
+        (Self { v: q }, Self { v: r })
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        self.clmul_wide(other).0
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        let a = self.v as u64;
+        let mut p: u64 = 0;
+        for i in 0..32 {
+            p ^= (a << i) & 0u64.wrapping_sub((other.v >> i) as u64 & 1);
+        }
+        (
+            Self { v: p as u32 },
+            Self {
+                v: (p >> 32) as u32,
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let s = self.v as u64 + other.v as u64 + carry as u64;
+        (Self { v: s as u32 }, (s >> 32) as u32)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let t = std::hint::black_box(choice).wrapping_neg() & (self.v ^ other.v);
+        (Self { v: self.v ^ t }, Self { v: other.v ^ t })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        let x = self.v ^ other.v;
+        std::hint::black_box(((x | x.wrapping_neg()) >> 31) ^ 1)
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let p = self.v as u64 * other.v as u64 + carry.v as u64;
+        (
+            Self { v: p as u32 },
+            Self {
+                v: (p >> 32) as u32,
+            },
+        )
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        let mask = std::hint::black_box(choice).wrapping_neg();
+        Self {
+            v: self.v ^ (mask & (self.v ^ other.v)),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let d = (self.v as u64)
+            .wrapping_sub(other.v as u64)
+            .wrapping_sub(borrow as u64);
+        (Self { v: d as u32 }, (d >> 63) as u32)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        (
+            Self {
+                v: self.v / divisor.v,
+            },
+            Self {
+                v: self.v % divisor.v,
+            },
+        )
+    }
+
     fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (v, r) = divisor.step(remainder, self.v);
+        (Self { v }, r)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let nv: u64 = (remainder as u64) << 32 | self.v as u64;
+        (
+            Self {
+                v: (nv / divisor as u64) as u32,
+            },
+            (nv % divisor as u64) as u32,
+        )
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        let (mut r0, mut r1) = (self.v, other.v);
+        let (mut s0, mut s1) = (1, 0);
+        let (mut t0, mut t1) = (0, 1);
+        let mut odd = false;
+        while r1 != 0 {
+            let q = r0 / r1;
+            let r = r0 - q * r1;
+            let s = s0 + q * s1;
+            let t = t0 + q * t1;
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        (Self { v: r0 }, Self { v: s0 }, Self { v: t0 }, odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self.v, other.v);
+        if a == 0 || b == 0 {
+            return Self { v: a | b };
+        }
+        let k = (a | b).trailing_zeros();
+        a >>= a.trailing_zeros();
+        loop {
+            b >>= b.trailing_zeros();
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            b -= a;
+            if b == 0 {
+                return Self { v: a << k };
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        if n == 1 || self.v <= 1 {
+            return self;
+        }
+        if n >= self.bits() {
+            return Self { v: 1 };
+        }
+        let v = self.v as u64;
+        let mut x = 1u64 << self.bits().div_ceil(n);
+        loop {
+            let y = ((n as u64 - 1) * x + v / x.pow(n - 1)) / n as u64;
+            if y >= x {
+                return Self { v: x as u32 };
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        self.iroot(2)
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (
+            s,
+            Self {
+                v: self.v - s.v * s.v,
+            },
+        )
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self.v == 0 || other.v == 0 {
+            return Some(self.zero());
+        }
+        (self.v / self.gcd(other).v)
+            .checked_mul(other.v)
+            .map(|v| Self { v })
+    }
+
+    fn limbs(self) -> usize {
+        1
     }
 
     fn zero(self) -> Self {
@@ -248,6 +1055,89 @@ impl Uintz for Uz32 {
         self.mulc32(other.v, carry)
     }
 
+    fn mul_hi(self, other: Self) -> Self {
+        Self {
+            v: ((self.v as u64 * other.v as u64) >> 32) as u32,
+        }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        Self {
+            v: self.v.wrapping_mul(other.v),
+        }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        Self {
+            v: (self.v as u64 * other.v as u64 % modulus.v as u64) as u32,
+        }
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        self.mulc(other, carry)
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        self.mulc(other, carry)
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        let m = modulus.v as u64;
+        let (mut b, mut e, mut r) = (self.v as u64 % m, exp.v, 1 % m);
+        while e != 0 {
+            if e & 1 != 0 {
+                r = r * b % m;
+            }
+            b = b * b % m;
+            e >>= 1;
+        }
+        Self { v: r as u32 }
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        self.wrapping_pow(exp.v)
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        Self { v: src[0] }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        Self {
+            v: self.v.saturating_pow(exp),
+        }
+    }
+
+    fn shl(self, n: u32) -> Self {
+        Self {
+            v: self.v.checked_shl(n).unwrap_or(0),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        Self {
+            v: self.v.checked_shr(n).unwrap_or(0),
+        }
+    }
+
+    fn square(self) -> Self {
+        Self {
+            v: self.v.wrapping_mul(self.v),
+        }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let m = modulus.v as u64;
+        Self {
+            v: ((self.v as u64 % m + m - other.v as u64 % m) % m) as u32,
+        }
+    }
+
     fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
         self.subb32(other.v, borrow)
     }
@@ -260,9 +1150,35 @@ impl Uintz for Uz32 {
         (Self { v: nv as u32 }, nb)
     }
 
+    fn lo32(self) -> u32 {
+        self.v
+    }
+
     fn max_value(self) -> Self {
+        Self { v: u32::MAX }
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        self.v.trailing_zeros()
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        self.mulc(self, self.zero())
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        Self {
+            v: self.v.wrapping_pow(exp),
+        }
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        dst[0] = self.v;
+    }
+
+    fn xor(self, other: Self) -> Self {
         Self {
-            v: u32::max_value(),
+            v: self.v ^ other.v,
         }
     }
 }
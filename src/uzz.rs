@@ -20,17 +20,12627 @@
 
 // This file was automatically generated by gen/uzz
 
+use crate::divisor::Divisor32;
+use crate::limbs;
+use crate::ntt;
+use crate::short;
+use crate::toom3;
 use crate::Uintz;
 use crate::Uz;
 use crate::Uz32;
 
+impl Uintz for Uz<Uz<Uz32>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz32>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz32>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz32>>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>>>> {
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (s, c) = a.addc(b, false);
+        if c || s >= modulus {
+            s.subb(modulus, false).0
+        } else {
+            s
+        }
+    }
+
+    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc(other.lo, carry);
+        let (hi, hic) = self.hi.addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
+        let (lo, loc) = self.lo.addc32(other, carry);
+        let (hi, hic) = self.hi.addc32(0, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn augment(self) -> Uz<Self> {
+        Uz {
+            hi: self.zero(),
+            lo: self,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        if self.hi != self.hi.zero() {
+            self.hi.bits() + self.lo.limbs() as u32 * 32
+        } else {
+            self.lo.bits()
+        }
+    }
+
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        let mut exp = exp;
+        loop {
+            if exp & 1 != 0 {
+                let (v, c) = acc.mulc(base, self.zero());
+                if c != self.zero() {
+                    return None;
+                }
+                acc = v;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return Some(acc);
+            }
+            let (v, c) = base.widening_square();
+            if c != self.zero() {
+                return None;
+            }
+            base = v;
+        }
+    }
+
+    fn cldivr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::cldivrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn clmul(self, other: Self) -> Self {
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let hl = self.hi.clmul(other.lo);
+        let lh = self.lo.clmul(other.hi);
+        Self {
+            hi: llc.xor(hl).xor(lh),
+            lo: ll,
+        }
+    }
+
+    fn clmul_wide(self, other: Self) -> (Self, Self) {
+        // Karatsuba needs no carries over GF(2): the middle term is
+        // (hi + lo)(other.hi + other.lo) - hh - ll with + and - as XOR.
+        let (ll, llc) = self.lo.clmul_wide(other.lo);
+        let (hh, hhc) = self.hi.clmul_wide(other.hi);
+        let (mm, mmc) = self.hi.xor(self.lo).clmul_wide(other.hi.xor(other.lo));
+        let (m, mc) = (mm.xor(ll).xor(hh), mmc.xor(llc).xor(hhc));
+        (
+            Self {
+                hi: llc.xor(m),
+                lo: ll,
+            },
+            Self {
+                hi: hhc,
+                lo: hh.xor(mc),
+            },
+        )
+    }
+
+    fn ct_addc(self, other: Self, carry: u32) -> (Self, u32) {
+        let (lo, loc) = self.lo.ct_addc(other.lo, carry);
+        let (hi, hic) = self.hi.ct_addc(other.hi, loc);
+        (Self { hi, lo }, hic)
+    }
+
+    fn ct_conditional_swap(self, other: Self, choice: u32) -> (Self, Self) {
+        let (hi, ohi) = self.hi.ct_conditional_swap(other.hi, choice);
+        let (lo, olo) = self.lo.ct_conditional_swap(other.lo, choice);
+        (Self { hi, lo }, Self { hi: ohi, lo: olo })
+    }
+
+    fn ct_eq(self, other: Self) -> u32 {
+        std::hint::black_box(self.hi.ct_eq(other.hi) & self.lo.ct_eq(other.lo))
+    }
+
+    fn ct_lt(self, other: Self) -> u32 {
+        std::hint::black_box(self.ct_subb(other, 0).1)
+    }
+
+    fn ct_mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        let (ll, llc) = self.lo.ct_mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.ct_mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.ct_mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.ct_mulc(other.hi, hlc);
+        let (ah, ahc) = lh.ct_addc(carry.hi, 0);
+        let (ac, acc) = lhc.ct_addc(hh, ahc);
+        let (aa, _) = hhc.ct_addc(hhc.zero(), acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn ct_select(self, other: Self, choice: u32) -> Self {
+        Self {
+            hi: self.hi.ct_select(other.hi, choice),
+            lo: self.lo.ct_select(other.lo, choice),
+        }
+    }
+
+    fn ct_subb(self, other: Self, borrow: u32) -> (Self, u32) {
+        let (lo, lob) = self.lo.ct_subb(other.lo, borrow);
+        let (hi, hib) = self.hi.ct_subb(other.hi, lob);
+        (Self { hi, lo }, hib)
+    }
+
+    fn divr(self, divisor: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        self.write_limbs(&mut a);
+        divisor.write_limbs(&mut b);
+        let (q, r) = limbs::divrem(&a, &b);
+        (self.read_limbs(&q), self.read_limbs(&r))
+    }
+
+    fn divr32(self, divisor: u32) -> (Self, u32) {
+        self.divr32c(divisor, 0)
+    }
+
+    fn divr32_pre(self, divisor: &Divisor32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32_pre(divisor, remainder);
+        let (lo, lor) = self.lo.divr32_pre(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn divr32c(self, divisor: u32, remainder: u32) -> (Self, u32) {
+        let (hi, hir) = self.hi.divr32c(divisor, remainder);
+        let (lo, lor) = self.lo.divr32c(divisor, hir);
+        (Self { hi, lo }, lor)
+    }
+
+    fn extended_gcd(self, other: Self) -> (Self, Self, Self, bool) {
+        // Runs on limb vectors, so that the deepest depths do not keep
+        // half a dozen full-width values per iteration on the stack.
+        let n = self.limbs();
+        let mut r0 = vec![0; n];
+        let mut r1 = vec![0; n];
+        self.write_limbs(&mut r0);
+        other.write_limbs(&mut r1);
+        let (mut s0, mut s1) = (vec![1], vec![0]);
+        let (mut t0, mut t1) = (vec![0], vec![1]);
+        let mut odd = false;
+        while !limbs::trimmed(&r1).is_empty() {
+            let (q, r) = limbs::divrem(&r0, &r1);
+            let q = limbs::trimmed(&q);
+            let s = limbs::add(&s0, &limbs::mul(q, limbs::trimmed(&s1)));
+            let t = limbs::add(&t0, &limbs::mul(q, limbs::trimmed(&t1)));
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+            odd = !odd;
+        }
+        let read = |v: &[u32]| {
+            let v = limbs::trimmed(v);
+            let mut l = vec![0; n];
+            l[..v.len()].copy_from_slice(v);
+            self.read_limbs(&l)
+        };
+        (read(&r0), read(&s0), read(&t0), odd)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == self.zero() {
+            return b;
+        }
+        if b == self.zero() {
+            return a;
+        }
+        let k = a.trailing_zeros().min(b.trailing_zeros());
+        a = a.shr(a.trailing_zeros());
+        loop {
+            b = b.shr(b.trailing_zeros());
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() > a.bits() + 32 {
+                b = b.divr(a).1;
+            } else {
+                b = b.subb(a, false).0;
+            }
+            if b == self.zero() {
+                return a.shl(k);
+            }
+        }
+    }
+
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (g, x, _, odd) = self.divr(modulus).1.extended_gcd(modulus);
+        if g != self.zero().addc32(1, false).0 {
+            return None;
+        }
+        if odd && x != self.zero() {
+            Some(modulus.subb(x, false).0)
+        } else {
+            Some(x)
+        }
+    }
+
+    fn iroot(self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        let one = self.zero().addc32(1, false).0;
+        if n == 1 || self <= one {
+            return self;
+        }
+        if n >= self.bits() {
+            return one;
+        }
+        let mut x = one.shl(self.bits().div_ceil(n));
+        loop {
+            let d = match x.checked_pow(n - 1) {
+                Some(p) => self.divr(p).0,
+                None => self.zero(),
+            };
+            let y = x.mulc32(n - 1, d).0.divr32(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn is_perfect_power(self) -> bool {
+        if self <= self.zero().addc32(1, false).0 {
+            return true;
+        }
+        (2..self.bits())
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .any(|k| self.iroot(k).checked_pow(k) == Some(self))
+    }
+
+    fn is_perfect_square(self) -> bool {
+        self.isqrt_rem().1 == self.zero()
+    }
+
+    fn isqrt(self) -> Self {
+        if self == self.zero() {
+            return self;
+        }
+        let mut x = self.zero().addc32(1, false).0.shl(self.bits().div_ceil(2));
+        loop {
+            let y = x.addc(self.divr(x).0, false).0.shr(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn isqrt_rem(self) -> (Self, Self) {
+        let s = self.isqrt();
+        (s, self.subb(s.square(), false).0)
+    }
+
+    fn lcm(self, other: Self) -> Option<Self> {
+        if self == self.zero() || other == self.zero() {
+            return Some(self.zero());
+        }
+        let (v, c) = self.divr(self.gcd(other)).0.mulc(other, self.zero());
+        if c != self.zero() {
+            return None;
+        }
+        Some(v)
+    }
+
+    fn limbs(self) -> usize {
+        2 * self.lo.limbs()
+    }
+
+    fn lo32(self) -> u32 {
+        self.lo.lo32()
+    }
+
+    fn max_value(self) -> Self {
+        Self {
+            hi: self.hi.max_value(),
+            lo: self.hi.max_value(),
+        }
+    }
+
+    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
+        if self.limbs() >= ntt::THRESHOLD {
+            return self.mul_ntt(other, carry);
+        }
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.mul_toom3(other, carry);
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
+        let (hl, hlc) = self.hi.mulc(other.lo, llc);
+        let (lh, lhc) = self.lo.mulc(other.hi, hl);
+        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
+        let (ah, ahc) = lh.addc(carry.hi, false);
+        let (ac, acc) = lhc.addc(hh, ahc);
+        let (aa, _) = hhc.addc32(0, acc);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
+        let (lo, loc) = self.lo.mulc32(other, carry.lo);
+        let (hi, hic) = self.hi.mulc32(other, loc);
+        (
+            Self { hi, lo },
+            Self {
+                hi: hic.zero(),
+                lo: hic,
+            },
+        )
+    }
+
+    fn mul_hi(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_hi(&a, &b));
+        }
+        // Two limbs need the same four word products either way, and mulc
+        // sums them with fewer carries.
+        if n == 2 {
+            return self.mulc(other, self.zero()).1;
+        }
+        let m = self.lo.mul_hi(other.lo);
+        let (x, xc) = self.hi.mulc(other.lo, m);
+        let (_, yc) = self.lo.mulc(other.hi, x);
+        let (hh, hhc) = self.hi.mulc(other.hi, xc);
+        let (lo, loc) = hh.addc(yc, false);
+        let (hi, _) = hhc.addc32(0, loc);
+        Self { hi, lo }
+    }
+
+    fn mul_lo(self, other: Self) -> Self {
+        let n = self.limbs();
+        if n >= short::THRESHOLD {
+            let mut a = vec![0; n];
+            let mut b = vec![0; n];
+            self.write_limbs(&mut a);
+            other.write_limbs(&mut b);
+            return self.read_limbs(&short::mul_lo(&a, &b));
+        }
+        let (ll, llc) = self.lo.mulc(other.lo, self.lo.zero());
+        let (hi, _) = llc.addc(self.hi.mul_lo(other.lo), false);
+        let (hi, _) = hi.addc(self.lo.mul_lo(other.hi), false);
+        Self { hi, lo: ll }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let n = self.limbs();
+        let (lo, hi) = self.mulc(other, self.zero());
+        let mut p = vec![0; 2 * n];
+        let mut m = vec![0; n];
+        lo.write_limbs(&mut p[..n]);
+        hi.write_limbs(&mut p[n..]);
+        modulus.write_limbs(&mut m);
+        let (_, r) = limbs::divrem(&p, &m);
+        self.read_limbs(&r)
+    }
+
+    fn mul_ntt(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = ntt::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn mul_toom3(self, other: Self, carry: Self) -> (Self, Self) {
+        let n = self.limbs();
+        let mut a = vec![0; n];
+        let mut b = vec![0; n];
+        let mut c = vec![0; n];
+        self.write_limbs(&mut a);
+        other.write_limbs(&mut b);
+        carry.write_limbs(&mut c);
+        let mut p = toom3::mul(&a, &b);
+        limbs::add_assign(&mut p, &c);
+        (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]))
+    }
+
+    fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to multiply with overflow")
+    }
+
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        // The window table and the running products live in limb vectors,
+        // which keeps them off the stack at the deepest depths.
+        let n = self.limbs();
+        let mut e = vec![0; n];
+        let mut m = vec![0; n];
+        let mut b = vec![0; n];
+        exp.write_limbs(&mut e);
+        modulus.write_limbs(&mut m);
+        self.write_limbs(&mut b);
+        let bit = |i: u32| e[i as usize / 32] >> (i % 32) & 1;
+        // The same product dispatch as mulc.
+        let mul = if n >= ntt::THRESHOLD {
+            ntt::mul
+        } else {
+            toom3::mul
+        };
+        let mul_mod = |x: &[u32], y: &[u32]| {
+            let mut r = limbs::divrem(&mul(x, y), &m).1;
+            r.resize(n, 0);
+            r
+        };
+        let window = match exp.bits() {
+            0..=16 => 1,
+            17..=128 => 3,
+            129..=1024 => 4,
+            _ => 5,
+        };
+        let base = limbs::divrem(&b, &m).1;
+        let base2 = mul_mod(&base, &base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 1) {
+            let next = mul_mod(&table[i - 1], &base2);
+            table.push(next);
+        }
+        b.iter_mut().for_each(|v| *v = 0);
+        b[0] = 1;
+        let mut acc = limbs::divrem(&b, &m).1;
+        let mut i = exp.bits();
+        while i > 0 {
+            if bit(i - 1) == 0 {
+                acc = mul_mod(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            let mut l = i.saturating_sub(window);
+            while bit(l) == 0 {
+                l += 1;
+            }
+            let mut v = 0;
+            for j in (l..i).rev() {
+                acc = mul_mod(&acc, &acc);
+                v = v << 1 | bit(j);
+            }
+            acc = mul_mod(&acc, &table[v as usize >> 1]);
+            i = l;
+        }
+        self.read_limbs(&acc)
+    }
+
+    fn pow_wide(self, exp: Self) -> Self {
+        let mut e = vec![0; exp.limbs()];
+        exp.write_limbs(&mut e);
+        let mut base = self;
+        let mut acc = self.zero().addc32(1, false).0;
+        for (i, &v) in e.iter().enumerate() {
+            let mut v = v;
+            for _ in 0..32 {
+                if v & 1 != 0 {
+                    acc = acc.mul_lo(base);
+                }
+                v >>= 1;
+                if v == 0 && e[i + 1..].iter().all(|&w| w == 0) {
+                    return acc;
+                }
+                base = base.square();
+            }
+        }
+        acc
+    }
+
+    fn read_limbs(self, src: &[u32]) -> Self {
+        let n = self.lo.limbs();
+        Self {
+            hi: self.hi.read_limbs(&src[n..]),
+            lo: self.lo.read_limbs(&src[..n]),
+        }
+    }
+
+    fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or_else(|| self.max_value())
+    }
+
+    fn shl(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.lo.shl(n - w),
+                lo: self.lo.zero(),
+            };
+        }
+        Self {
+            hi: self.hi.shl(n).addc(self.lo.shr(w - n), false).0,
+            lo: self.lo.shl(n),
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        let w = self.lo.limbs() as u32 * 32;
+        if n >= w {
+            return Self {
+                hi: self.hi.zero(),
+                lo: self.hi.shr(n - w),
+            };
+        }
+        Self {
+            hi: self.hi.shr(n),
+            lo: self.lo.shr(n).addc(self.hi.shl(w - n), false).0,
+        }
+    }
+
+    fn square(self) -> Self {
+        if self.limbs() >= toom3::THRESHOLD {
+            return self.widening_square().0;
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let hl = self.hi.mul_lo(self.lo);
+        let (hi, _) = llc.addc(hl, false);
+        let (hi, _) = hi.addc(hl, false);
+        Self { hi, lo: ll }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        let a = if self >= modulus {
+            self.divr(modulus).1
+        } else {
+            self
+        };
+        let b = if other >= modulus {
+            other.divr(modulus).1
+        } else {
+            other
+        };
+        let (d, b) = a.subb(b, false);
+        if b {
+            d.addc(modulus, false).0
+        } else {
+            d
+        }
+    }
+
+    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb(other.lo, borrow);
+        let (hi, hib) = self.hi.subb(other.hi, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
+        let (lo, lob) = self.lo.subb32(other, borrow);
+        let (hi, hib) = self.hi.subb32(0, lob);
+        (Self { lo, hi }, hib)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == self.lo.zero() {
+            self.lo.limbs() as u32 * 32 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn widening_square(self) -> (Self, Self) {
+        let n = self.limbs();
+        if n >= toom3::THRESHOLD {
+            let mut a = vec![0; n];
+            self.write_limbs(&mut a);
+            let p = if n >= ntt::THRESHOLD {
+                ntt::sqr(&a)
+            } else {
+                toom3::sqr(&a)
+            };
+            return (self.read_limbs(&p[..n]), self.read_limbs(&p[n..]));
+        }
+        let (ll, llc) = self.lo.widening_square();
+        let (hl, hlc) = self.hi.mulc(self.lo, self.lo.zero());
+        let (hh, hhc) = self.hi.widening_square();
+        let (ah, ahc) = llc.addc(hl, false);
+        let (ah, ahc2) = ah.addc(hl, false);
+        let (ac, acc) = hh.addc(hlc, ahc);
+        let (ac, acc2) = ac.addc(hlc, ahc2);
+        let (aa, _) = hhc.addc32(acc as u32, acc2);
+        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    }
+
+    fn wrapping_pow(self, exp: u32) -> Self {
+        self.pow_wide(self.zero().addc32(exp, false).0)
+    }
+
+    fn write_limbs(self, dst: &mut [u32]) {
+        let n = self.lo.limbs();
+        self.lo.write_limbs(&mut dst[..n]);
+        self.hi.write_limbs(&mut dst[n..]);
+    }
+
+    fn xor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi.xor(other.hi),
+            lo: self.lo.xor(other.lo),
+        }
+    }
+
+    fn zero(self) -> Self {
+        Self {
+            hi: self.hi.zero(),
+            lo: self.hi.zero(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod testsz {
+
+    use crate::*;
+
+    fn new(v: u32) -> Uz<Uz32> {
+        from_u32(v).augment()
+    }
+
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
+    }
+
+    #[test]
+    fn eq0() {
+        assert_eq!(new(0), new(0));
+    }
+
+    #[test]
+    fn eq1() {
+        assert_ne!(new(0), new(1));
+    }
+
+    #[test]
+    fn eq2() {
+        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    }
+
+    #[test]
+    fn ord0() {
+        assert!(new(0) < new(1));
+    }
+
+    #[test]
+    fn ord1() {
+        assert!(new(0) < new(u32::max_value()));
+    }
+
+    #[test]
+    fn min_value0() {
+        assert_eq!(new(u32::max_value()).zero(), new(0));
+    }
+
+    #[test]
+    fn augment0() {
+        let v = new(u32::max_value());
+        let va = v.augment();
+        assert_eq!(
+            va,
+            Uz {
+                hi: v.zero(),
+                lo: v,
+            }
+        );
+    }
+
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
+    #[test]
+    fn addc0() {
+        let (v, c) = new(0).addc(new(1), false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc1() {
+        let (v, c) = new(0).addc(new(1), true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc2() {
+        let (v, c) = new(0).max_value().addc(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn addc32_0() {
+        let (v, c) = new(0).addc32(1, false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_1() {
+        let (v, c) = new(0).addc32(1, true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_2() {
+        let (v, c) = new(0).max_value().addc32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
+    }
+
+    #[test]
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
+    }
+
+    #[test]
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
+    }
+
+    #[test]
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
+    }
+
+    #[test]
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
+    }
+
+    #[test]
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
+    }
+
+    #[test]
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
+    }
+
+    #[test]
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
+    }
+
+    #[test]
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
+    }
+
+    #[test]
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
+
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
+
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
+
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
+    }
+
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
+    }
+
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
+
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+    }
+
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
+
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
+
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
+    }
+
+    #[test]
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
+    }
+
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
+    #[test]
+    fn subb0() {
+        let (v, c) = new(1).subb(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb1() {
+        let (v, c) = new(0).subb(new(1), false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb2() {
+        let (v, c) = new(1).subb(new(1), true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_0() {
+        let (v, c) = new(1).subb32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb32_1() {
+        let (v, c) = new(0).subb32(1, false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_2() {
+        let (v, c) = new(1).subb32(1, true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
+    }
+}
+
+#[cfg(test)]
+mod testszz {
+
+    use crate::*;
+
+    fn new(v: u32) -> Uz<Uz<Uz32>> {
+        from_u32(v).augment().augment()
+    }
+
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
+    }
+
+    #[test]
+    fn eq0() {
+        assert_eq!(new(0), new(0));
+    }
+
+    #[test]
+    fn eq1() {
+        assert_ne!(new(0), new(1));
+    }
+
+    #[test]
+    fn eq2() {
+        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    }
+
+    #[test]
+    fn ord0() {
+        assert!(new(0) < new(1));
+    }
+
+    #[test]
+    fn ord1() {
+        assert!(new(0) < new(u32::max_value()));
+    }
+
+    #[test]
+    fn min_value0() {
+        assert_eq!(new(u32::max_value()).zero(), new(0));
+    }
+
+    #[test]
+    fn augment0() {
+        let v = new(u32::max_value());
+        let va = v.augment();
+        assert_eq!(
+            va,
+            Uz {
+                hi: v.zero(),
+                lo: v,
+            }
+        );
+    }
+
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
+    #[test]
+    fn addc0() {
+        let (v, c) = new(0).addc(new(1), false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc1() {
+        let (v, c) = new(0).addc(new(1), true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc2() {
+        let (v, c) = new(0).max_value().addc(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn addc32_0() {
+        let (v, c) = new(0).addc32(1, false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_1() {
+        let (v, c) = new(0).addc32(1, true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_2() {
+        let (v, c) = new(0).max_value().addc32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
+    }
+
+    #[test]
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
+    }
+
+    #[test]
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
+    }
+
+    #[test]
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
+    }
+
+    #[test]
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
+    }
+
+    #[test]
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
+    }
+
+    #[test]
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
+    }
+
+    #[test]
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
+    }
+
+    #[test]
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
+    }
+
+    #[test]
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
+
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
+
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
+
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
+    }
+
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
+    }
+
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
+
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+    }
+
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
+
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
+
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
+    }
+
+    #[test]
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
+    }
+
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
+    #[test]
+    fn subb0() {
+        let (v, c) = new(1).subb(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb1() {
+        let (v, c) = new(0).subb(new(1), false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb2() {
+        let (v, c) = new(1).subb(new(1), true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_0() {
+        let (v, c) = new(1).subb32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb32_1() {
+        let (v, c) = new(0).subb32(1, false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_2() {
+        let (v, c) = new(1).subb32(1, true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
+    }
+}
+
+#[cfg(test)]
+mod testszzz {
+
+    use crate::*;
+
+    fn new(v: u32) -> Uz<Uz<Uz<Uz32>>> {
+        from_u32(v).augment().augment().augment()
+    }
+
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
+    }
+
+    #[test]
+    fn eq0() {
+        assert_eq!(new(0), new(0));
+    }
+
+    #[test]
+    fn eq1() {
+        assert_ne!(new(0), new(1));
+    }
+
+    #[test]
+    fn eq2() {
+        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    }
+
+    #[test]
+    fn ord0() {
+        assert!(new(0) < new(1));
+    }
+
+    #[test]
+    fn ord1() {
+        assert!(new(0) < new(u32::max_value()));
+    }
+
+    #[test]
+    fn min_value0() {
+        assert_eq!(new(u32::max_value()).zero(), new(0));
+    }
+
+    #[test]
+    fn augment0() {
+        let v = new(u32::max_value());
+        let va = v.augment();
+        assert_eq!(
+            va,
+            Uz {
+                hi: v.zero(),
+                lo: v,
+            }
+        );
+    }
+
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
+    #[test]
+    fn addc0() {
+        let (v, c) = new(0).addc(new(1), false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc1() {
+        let (v, c) = new(0).addc(new(1), true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc2() {
+        let (v, c) = new(0).max_value().addc(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn addc32_0() {
+        let (v, c) = new(0).addc32(1, false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_1() {
+        let (v, c) = new(0).addc32(1, true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_2() {
+        let (v, c) = new(0).max_value().addc32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
+    }
+
+    #[test]
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
+    }
+
+    #[test]
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
+    }
+
+    #[test]
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
+    }
+
+    #[test]
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
+    }
+
+    #[test]
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
+    }
+
+    #[test]
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
+    }
+
+    #[test]
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
+    }
+
+    #[test]
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
+    }
+
+    #[test]
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
+
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
+
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
+
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
+    }
+
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
+    }
+
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
+
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+    }
+
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
+
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
+
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
+    }
+
+    #[test]
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
+    }
+
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
+    #[test]
+    fn subb0() {
+        let (v, c) = new(1).subb(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb1() {
+        let (v, c) = new(0).subb(new(1), false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb2() {
+        let (v, c) = new(1).subb(new(1), true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_0() {
+        let (v, c) = new(1).subb32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb32_1() {
+        let (v, c) = new(0).subb32(1, false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_2() {
+        let (v, c) = new(1).subb32(1, true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
+    }
+}
+
+#[cfg(test)]
+mod testszzzz {
+
+    use crate::*;
+
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz32>>>> {
+        from_u32(v).augment().augment().augment().augment()
+    }
+
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
+    }
+
+    #[test]
+    fn eq0() {
+        assert_eq!(new(0), new(0));
+    }
+
+    #[test]
+    fn eq1() {
+        assert_ne!(new(0), new(1));
+    }
+
+    #[test]
+    fn eq2() {
+        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    }
+
+    #[test]
+    fn ord0() {
+        assert!(new(0) < new(1));
+    }
+
+    #[test]
+    fn ord1() {
+        assert!(new(0) < new(u32::max_value()));
+    }
+
+    #[test]
+    fn min_value0() {
+        assert_eq!(new(u32::max_value()).zero(), new(0));
+    }
+
+    #[test]
+    fn augment0() {
+        let v = new(u32::max_value());
+        let va = v.augment();
+        assert_eq!(
+            va,
+            Uz {
+                hi: v.zero(),
+                lo: v,
+            }
+        );
+    }
+
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
+    #[test]
+    fn addc0() {
+        let (v, c) = new(0).addc(new(1), false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc1() {
+        let (v, c) = new(0).addc(new(1), true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc2() {
+        let (v, c) = new(0).max_value().addc(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn addc32_0() {
+        let (v, c) = new(0).addc32(1, false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_1() {
+        let (v, c) = new(0).addc32(1, true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_2() {
+        let (v, c) = new(0).max_value().addc32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
+    }
+
+    #[test]
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
+    }
+
+    #[test]
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
+    }
+
+    #[test]
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
+    }
+
+    #[test]
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
+    }
+
+    #[test]
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
+    }
+
+    #[test]
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
+    }
+
+    #[test]
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
+    }
+
+    #[test]
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
+    }
+
+    #[test]
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
+
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
+
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
+
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
+    }
+
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
+    }
+
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
+
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+    }
+
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
+
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
+
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
+    }
+
+    #[test]
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
+    }
+
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
+    #[test]
+    fn subb0() {
+        let (v, c) = new(1).subb(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb1() {
+        let (v, c) = new(0).subb(new(1), false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb2() {
+        let (v, c) = new(1).subb(new(1), true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_0() {
+        let (v, c) = new(1).subb32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb32_1() {
+        let (v, c) = new(0).subb32(1, false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_2() {
+        let (v, c) = new(1).subb32(1, true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
+    }
+}
+
+#[cfg(test)]
+mod testszzzzz {
+
+    use crate::*;
+
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz32>>>>> {
+        from_u32(v).augment().augment().augment().augment().augment()
+    }
+
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
+    }
+
+    #[test]
+    fn eq0() {
+        assert_eq!(new(0), new(0));
+    }
+
+    #[test]
+    fn eq1() {
+        assert_ne!(new(0), new(1));
+    }
+
+    #[test]
+    fn eq2() {
+        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    }
+
+    #[test]
+    fn ord0() {
+        assert!(new(0) < new(1));
+    }
+
+    #[test]
+    fn ord1() {
+        assert!(new(0) < new(u32::max_value()));
+    }
+
+    #[test]
+    fn min_value0() {
+        assert_eq!(new(u32::max_value()).zero(), new(0));
+    }
+
+    #[test]
+    fn augment0() {
+        let v = new(u32::max_value());
+        let va = v.augment();
+        assert_eq!(
+            va,
+            Uz {
+                hi: v.zero(),
+                lo: v,
+            }
+        );
+    }
+
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
+    #[test]
+    fn addc0() {
+        let (v, c) = new(0).addc(new(1), false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc1() {
+        let (v, c) = new(0).addc(new(1), true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc2() {
+        let (v, c) = new(0).max_value().addc(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn addc32_0() {
+        let (v, c) = new(0).addc32(1, false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_1() {
+        let (v, c) = new(0).addc32(1, true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_2() {
+        let (v, c) = new(0).max_value().addc32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
+    }
+
+    #[test]
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
+    }
+
+    #[test]
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
+    }
+
+    #[test]
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
+    }
+
+    #[test]
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
+    }
+
+    #[test]
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
+    }
+
+    #[test]
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
+    }
+
+    #[test]
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
+    }
+
+    #[test]
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
+    }
+
+    #[test]
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
+
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
+
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
+
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
+    }
+
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
+    }
+
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
+
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+    }
+
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
+
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
+
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
+    }
+
+    #[test]
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
+    }
+
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
+    #[test]
+    fn subb0() {
+        let (v, c) = new(1).subb(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb1() {
+        let (v, c) = new(0).subb(new(1), false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb2() {
+        let (v, c) = new(1).subb(new(1), true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_0() {
+        let (v, c) = new(1).subb32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb32_1() {
+        let (v, c) = new(0).subb32(1, false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_2() {
+        let (v, c) = new(1).subb32(1, true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
+    }
+}
+
+#[cfg(test)]
+mod testszzzzzz {
+
+    use crate::*;
+
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>> {
+        from_u32(v).augment().augment().augment().augment().augment().augment()
+    }
+
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
+    }
+
+    #[test]
+    fn eq0() {
+        assert_eq!(new(0), new(0));
+    }
+
+    #[test]
+    fn eq1() {
+        assert_ne!(new(0), new(1));
+    }
+
+    #[test]
+    fn eq2() {
+        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    }
+
+    #[test]
+    fn ord0() {
+        assert!(new(0) < new(1));
+    }
+
+    #[test]
+    fn ord1() {
+        assert!(new(0) < new(u32::max_value()));
+    }
+
+    #[test]
+    fn min_value0() {
+        assert_eq!(new(u32::max_value()).zero(), new(0));
+    }
+
+    #[test]
+    fn augment0() {
+        let v = new(u32::max_value());
+        let va = v.augment();
+        assert_eq!(
+            va,
+            Uz {
+                hi: v.zero(),
+                lo: v,
+            }
+        );
+    }
+
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
+    #[test]
+    fn addc0() {
+        let (v, c) = new(0).addc(new(1), false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc1() {
+        let (v, c) = new(0).addc(new(1), true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc2() {
+        let (v, c) = new(0).max_value().addc(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn addc32_0() {
+        let (v, c) = new(0).addc32(1, false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_1() {
+        let (v, c) = new(0).addc32(1, true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_2() {
+        let (v, c) = new(0).max_value().addc32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
+    }
+
+    #[test]
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
+    }
+
+    #[test]
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
+    }
+
+    #[test]
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
+    }
+
+    #[test]
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
+    }
+
+    #[test]
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
+    }
+
+    #[test]
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
+    }
+
+    #[test]
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
+    }
+
+    #[test]
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
+    }
+
+    #[test]
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
+
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
+
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
+
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
+    }
+
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
+    }
+
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
+
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+    }
+
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
+
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
+
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
+    }
+
+    #[test]
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
+    }
+
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
+    #[test]
+    fn subb0() {
+        let (v, c) = new(1).subb(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb1() {
+        let (v, c) = new(0).subb(new(1), false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb2() {
+        let (v, c) = new(1).subb(new(1), true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_0() {
+        let (v, c) = new(1).subb32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb32_1() {
+        let (v, c) = new(0).subb32(1, false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_2() {
+        let (v, c) = new(1).subb32(1, true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
+    }
+}
+
+#[cfg(test)]
+mod testszzzzzzz {
+
+    use crate::*;
+
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>> {
+        from_u32(v).augment().augment().augment().augment().augment().augment().augment()
+    }
+
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
+    }
+
+    #[test]
+    fn eq0() {
+        assert_eq!(new(0), new(0));
+    }
+
+    #[test]
+    fn eq1() {
+        assert_ne!(new(0), new(1));
+    }
+
+    #[test]
+    fn eq2() {
+        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    }
+
+    #[test]
+    fn ord0() {
+        assert!(new(0) < new(1));
+    }
+
+    #[test]
+    fn ord1() {
+        assert!(new(0) < new(u32::max_value()));
+    }
+
+    #[test]
+    fn min_value0() {
+        assert_eq!(new(u32::max_value()).zero(), new(0));
+    }
+
+    #[test]
+    fn augment0() {
+        let v = new(u32::max_value());
+        let va = v.augment();
+        assert_eq!(
+            va,
+            Uz {
+                hi: v.zero(),
+                lo: v,
+            }
+        );
+    }
+
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
+    #[test]
+    fn addc0() {
+        let (v, c) = new(0).addc(new(1), false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc1() {
+        let (v, c) = new(0).addc(new(1), true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc2() {
+        let (v, c) = new(0).max_value().addc(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn addc32_0() {
+        let (v, c) = new(0).addc32(1, false);
+        assert_eq!(v, new(1));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_1() {
+        let (v, c) = new(0).addc32(1, true);
+        assert_eq!(v, new(2));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn addc32_2() {
+        let (v, c) = new(0).max_value().addc32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
+    }
+
+    #[test]
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
+    }
+
+    #[test]
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
+    }
+
+    #[test]
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
+    }
+
+    #[test]
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
+    }
+
+    #[test]
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
+    }
+
+    #[test]
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
+    }
+
+    #[test]
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
+    }
+
+    #[test]
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
+    }
+
+    #[test]
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
+
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
+
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
+
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
+    }
+
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
+    }
+
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
+
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+    }
+
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
+
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
+
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
+    }
+
+    #[test]
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
+    }
+
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
+    #[test]
+    fn subb0() {
+        let (v, c) = new(1).subb(new(1), false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb1() {
+        let (v, c) = new(0).subb(new(1), false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb2() {
+        let (v, c) = new(1).subb(new(1), true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_0() {
+        let (v, c) = new(1).subb32(1, false);
+        assert_eq!(v, new(0));
+        assert_eq!(c, false);
+    }
+
+    #[test]
+    fn subb32_1() {
+        let (v, c) = new(0).subb32(1, false);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn subb32_2() {
+        let (v, c) = new(1).subb32(1, true);
+        assert_eq!(v, new(0).max_value());
+        assert_eq!(c, true);
+    }
+
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
+    }
+}
+
 #[cfg(test)]
-mod testsz {
+mod testszzzzzzzz {
 
     use crate::*;
 
-    fn new(v: u32) -> Uz<Uz32> {
-        from_u32(v).augment()
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>> {
+        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment()
+    }
+
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
+    }
+
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
     }
 
     #[test]
@@ -76,6 +12686,18 @@ mod testsz {
         );
     }
 
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
     #[test]
     fn addc0() {
         let (v, c) = new(0).addc(new(1), false);
@@ -119,183 +12741,364 @@ mod testsz {
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
     }
 
     #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
     }
 
     #[test]
-    fn subb0() {
-        let (v, c) = new(1).subb(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
     }
 
     #[test]
-    fn subb1() {
-        let (v, c) = new(0).subb(new(1), false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
     }
 
     #[test]
-    fn subb2() {
-        let (v, c) = new(1).subb(new(1), true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
     }
 
     #[test]
-    fn subb32_0() {
-        let (v, c) = new(1).subb32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
     }
 
     #[test]
-    fn subb32_1() {
-        let (v, c) = new(0).subb32(1, false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
     }
 
     #[test]
-    fn subb32_2() {
-        let (v, c) = new(1).subb32(1, true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
     }
 
-}
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
 
-#[cfg(test)]
-mod testszz {
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
+    }
 
-    use crate::*;
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
+    }
 
-    fn new(v: u32) -> Uz<Uz<Uz32>> {
-        from_u32(v).augment().augment()
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
     }
 
     #[test]
-    fn eq0() {
-        assert_eq!(new(0), new(0));
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
     }
 
     #[test]
-    fn eq1() {
-        assert_ne!(new(0), new(1));
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
     }
 
     #[test]
-    fn eq2() {
-        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
     }
 
     #[test]
-    fn ord0() {
-        assert!(new(0) < new(1));
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
     }
 
     #[test]
-    fn ord1() {
-        assert!(new(0) < new(u32::max_value()));
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
     }
 
     #[test]
-    fn min_value0() {
-        assert_eq!(new(u32::max_value()).zero(), new(0));
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
     }
 
     #[test]
-    fn augment0() {
-        let v = new(u32::max_value());
-        let va = v.augment();
-        assert_eq!(
-            va,
-            Uz {
-                hi: v.zero(),
-                lo: v,
-            }
-        );
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
     }
 
     #[test]
-    fn addc0() {
-        let (v, c) = new(0).addc(new(1), false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn addc1() {
-        let (v, c) = new(0).addc(new(1), true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
     }
 
     #[test]
-    fn addc2() {
-        let (v, c) = new(0).max_value().addc(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
     }
 
     #[test]
-    fn addc32_0() {
-        let (v, c) = new(0).addc32(1, false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
     }
 
     #[test]
-    fn addc32_1() {
-        let (v, c) = new(0).addc32(1, true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
     }
 
     #[test]
-    fn addc32_2() {
-        let (v, c) = new(0).max_value().addc32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
     }
 
     #[test]
@@ -313,31 +13116,209 @@ mod testszz {
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
     }
 
     #[test]
@@ -382,89 +13363,43 @@ mod testszz {
         assert_eq!(c, true);
     }
 
-}
-
-impl Uintz for Uz<Uz<Uz32>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
-    }
-
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
-    }
-
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
-    }
-
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
     }
+}
 
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
-    }
+#[cfg(test)]
+mod testszzzzzzzzz {
 
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
-    }
+    use crate::*;
 
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>> {
+        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment().augment()
     }
 
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
     }
-}
-
-#[cfg(test)]
-mod testszzz {
 
-    use crate::*;
-
-    fn new(v: u32) -> Uz<Uz<Uz<Uz32>>> {
-        from_u32(v).augment().augment().augment()
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
     }
 
     #[test]
@@ -510,6 +13445,18 @@ mod testszzz {
         );
     }
 
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
     #[test]
     fn addc0() {
         let (v, c) = new(0).addc(new(1), false);
@@ -553,553 +13500,584 @@ mod testszzz {
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
-    }
-
-    #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
-    }
-
-    #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
-    }
-
-    #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
-    }
-
-    #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
-    }
-
-    #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
     }
 
     #[test]
-    fn subb0() {
-        let (v, c) = new(1).subb(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
     }
 
     #[test]
-    fn subb1() {
-        let (v, c) = new(0).subb(new(1), false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
     }
 
     #[test]
-    fn subb2() {
-        let (v, c) = new(1).subb(new(1), true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
     }
 
     #[test]
-    fn subb32_0() {
-        let (v, c) = new(1).subb32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
     }
 
     #[test]
-    fn subb32_1() {
-        let (v, c) = new(0).subb32(1, false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
     }
 
     #[test]
-    fn subb32_2() {
-        let (v, c) = new(1).subb32(1, true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
-    }
-
-}
-
-impl Uintz for Uz<Uz<Uz<Uz32>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
-    }
-
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
-    }
-
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
-    }
-
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
-    }
-
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
-    }
-
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
-    }
-
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
-    }
-
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
-    }
-}
-
-#[cfg(test)]
-mod testszzzz {
-
-    use crate::*;
-
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz32>>>> {
-        from_u32(v).augment().augment().augment().augment()
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
     }
 
     #[test]
-    fn eq0() {
-        assert_eq!(new(0), new(0));
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
     }
 
     #[test]
-    fn eq1() {
-        assert_ne!(new(0), new(1));
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
     }
 
     #[test]
-    fn eq2() {
-        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
     }
 
     #[test]
-    fn ord0() {
-        assert!(new(0) < new(1));
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
     }
 
     #[test]
-    fn ord1() {
-        assert!(new(0) < new(u32::max_value()));
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
     }
 
     #[test]
-    fn min_value0() {
-        assert_eq!(new(u32::max_value()).zero(), new(0));
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
     }
 
     #[test]
-    fn augment0() {
-        let v = new(u32::max_value());
-        let va = v.augment();
-        assert_eq!(
-            va,
-            Uz {
-                hi: v.zero(),
-                lo: v,
-            }
-        );
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
     }
 
     #[test]
-    fn addc0() {
-        let (v, c) = new(0).addc(new(1), false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
     }
 
     #[test]
-    fn addc1() {
-        let (v, c) = new(0).addc(new(1), true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
     }
 
     #[test]
-    fn addc2() {
-        let (v, c) = new(0).max_value().addc(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
     }
 
     #[test]
-    fn addc32_0() {
-        let (v, c) = new(0).addc32(1, false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
     }
 
     #[test]
-    fn addc32_1() {
-        let (v, c) = new(0).addc32(1, true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
     }
 
     #[test]
-    fn addc32_2() {
-        let (v, c) = new(0).max_value().addc32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
     }
 
     #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
         assert_eq!(c, new(0));
+        assert_eq!(r, 0);
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
         assert_eq!(c, new(0));
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
     }
 
-    #[test]
-    fn subb0() {
-        let (v, c) = new(1).subb(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
     }
 
     #[test]
-    fn subb1() {
-        let (v, c) = new(0).subb(new(1), false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
     }
 
     #[test]
-    fn subb2() {
-        let (v, c) = new(1).subb(new(1), true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
     }
 
     #[test]
-    fn subb32_0() {
-        let (v, c) = new(1).subb32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
     }
 
     #[test]
-    fn subb32_1() {
-        let (v, c) = new(0).subb32(1, false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
     }
 
     #[test]
-    fn subb32_2() {
-        let (v, c) = new(1).subb32(1, true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
     }
 
-}
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
 
-impl Uintz for Uz<Uz<Uz<Uz<Uz32>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
     }
 
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
     }
 
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
     }
 
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
     }
 
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
     }
 
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
     }
 
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
     }
 
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
     }
-}
 
-#[cfg(test)]
-mod testszzzzz {
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
 
-    use crate::*;
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
 
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz32>>>>> {
-        from_u32(v).augment().augment().augment().augment().augment()
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
     }
 
     #[test]
-    fn eq0() {
-        assert_eq!(new(0), new(0));
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
     }
 
     #[test]
-    fn eq1() {
-        assert_ne!(new(0), new(1));
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn eq2() {
-        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
     }
 
     #[test]
-    fn ord0() {
-        assert!(new(0) < new(1));
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
     #[test]
-    fn ord1() {
-        assert!(new(0) < new(u32::max_value()));
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn min_value0() {
-        assert_eq!(new(u32::max_value()).zero(), new(0));
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
     }
 
     #[test]
-    fn augment0() {
-        let v = new(u32::max_value());
-        let va = v.augment();
-        assert_eq!(
-            va,
-            Uz {
-                hi: v.zero(),
-                lo: v,
-            }
-        );
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
     #[test]
-    fn addc0() {
-        let (v, c) = new(0).addc(new(1), false);
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
         assert_eq!(v, new(1));
-        assert_eq!(c, false);
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn addc1() {
-        let (v, c) = new(0).addc(new(1), true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn addc2() {
-        let (v, c) = new(0).max_value().addc(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
     #[test]
-    fn addc32_0() {
-        let (v, c) = new(0).addc32(1, false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
     }
 
     #[test]
-    fn addc32_1() {
-        let (v, c) = new(0).addc32(1, true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
     }
 
     #[test]
-    fn addc32_2() {
-        let (v, c) = new(0).max_value().addc32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
     }
 
     #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
     }
 
     #[test]
@@ -1144,89 +14122,43 @@ mod testszzzzz {
         assert_eq!(c, true);
     }
 
-}
-
-impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz32>>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
-    }
-
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
-    }
-
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
-    }
-
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
     }
+}
 
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
-    }
+#[cfg(test)]
+mod testszzzzzzzzzz {
 
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
-    }
+    use crate::*;
 
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>> {
+        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment().augment().augment()
     }
 
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
     }
-}
-
-#[cfg(test)]
-mod testszzzzzz {
 
-    use crate::*;
-
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>> {
-        from_u32(v).augment().augment().augment().augment().augment().augment()
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
     }
 
     #[test]
@@ -1272,6 +14204,18 @@ mod testszzzzzz {
         );
     }
 
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
     #[test]
     fn addc0() {
         let (v, c) = new(0).addc(new(1), false);
@@ -1315,553 +14259,584 @@ mod testszzzzzz {
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
-    }
-
-    #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
     }
 
     #[test]
-    fn subb0() {
-        let (v, c) = new(1).subb(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
     }
 
     #[test]
-    fn subb1() {
-        let (v, c) = new(0).subb(new(1), false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
     }
 
     #[test]
-    fn subb2() {
-        let (v, c) = new(1).subb(new(1), true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
     }
 
     #[test]
-    fn subb32_0() {
-        let (v, c) = new(1).subb32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
     }
 
     #[test]
-    fn subb32_1() {
-        let (v, c) = new(0).subb32(1, false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
     }
 
     #[test]
-    fn subb32_2() {
-        let (v, c) = new(1).subb32(1, true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
-    }
-
-}
-
-impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
-    }
-
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
-    }
-
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
-    }
-
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
-    }
-
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
-    }
-
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
-    }
-
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
-    }
-
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
         }
     }
-}
-
-#[cfg(test)]
-mod testszzzzzzz {
-
-    use crate::*;
-
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>> {
-        from_u32(v).augment().augment().augment().augment().augment().augment().augment()
-    }
-
-    #[test]
-    fn eq0() {
-        assert_eq!(new(0), new(0));
-    }
-
-    #[test]
-    fn eq1() {
-        assert_ne!(new(0), new(1));
-    }
-
-    #[test]
-    fn eq2() {
-        assert_eq!(new(u32::max_value()), new(u32::max_value()));
-    }
-
-    #[test]
-    fn ord0() {
-        assert!(new(0) < new(1));
-    }
 
     #[test]
-    fn ord1() {
-        assert!(new(0) < new(u32::max_value()));
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
     }
 
     #[test]
-    fn min_value0() {
-        assert_eq!(new(u32::max_value()).zero(), new(0));
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
     }
 
     #[test]
-    fn augment0() {
-        let v = new(u32::max_value());
-        let va = v.augment();
-        assert_eq!(
-            va,
-            Uz {
-                hi: v.zero(),
-                lo: v,
-            }
-        );
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
     }
 
     #[test]
-    fn addc0() {
-        let (v, c) = new(0).addc(new(1), false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
     }
 
     #[test]
-    fn addc1() {
-        let (v, c) = new(0).addc(new(1), true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
     }
 
     #[test]
-    fn addc2() {
-        let (v, c) = new(0).max_value().addc(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
     }
 
     #[test]
-    fn addc32_0() {
-        let (v, c) = new(0).addc32(1, false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
     }
 
     #[test]
-    fn addc32_1() {
-        let (v, c) = new(0).addc32(1, true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
     }
 
     #[test]
-    fn addc32_2() {
-        let (v, c) = new(0).max_value().addc32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
+        }
     }
 
     #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
         assert_eq!(c, new(0));
+        assert_eq!(r, 0);
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
         assert_eq!(c, new(0));
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
     }
 
-    #[test]
-    fn subb0() {
-        let (v, c) = new(1).subb(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
     }
 
     #[test]
-    fn subb1() {
-        let (v, c) = new(0).subb(new(1), false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
     }
 
     #[test]
-    fn subb2() {
-        let (v, c) = new(1).subb(new(1), true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
     }
 
     #[test]
-    fn subb32_0() {
-        let (v, c) = new(1).subb32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
     }
 
     #[test]
-    fn subb32_1() {
-        let (v, c) = new(0).subb32(1, false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
     }
 
     #[test]
-    fn subb32_2() {
-        let (v, c) = new(1).subb32(1, true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
     }
 
-}
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
 
-impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
     }
 
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
     }
 
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
     }
 
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
     }
 
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
     }
 
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    #[test]
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
     }
 
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
+    #[test]
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
+    #[test]
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
     }
 
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
     }
-}
 
-#[cfg(test)]
-mod testszzzzzzzz {
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
 
-    use crate::*;
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
 
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>> {
-        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment()
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
     }
 
     #[test]
-    fn eq0() {
-        assert_eq!(new(0), new(0));
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
     }
 
     #[test]
-    fn eq1() {
-        assert_ne!(new(0), new(1));
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn eq2() {
-        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
     }
 
     #[test]
-    fn ord0() {
-        assert!(new(0) < new(1));
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
     #[test]
-    fn ord1() {
-        assert!(new(0) < new(u32::max_value()));
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn min_value0() {
-        assert_eq!(new(u32::max_value()).zero(), new(0));
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
     }
 
     #[test]
-    fn augment0() {
-        let v = new(u32::max_value());
-        let va = v.augment();
-        assert_eq!(
-            va,
-            Uz {
-                hi: v.zero(),
-                lo: v,
-            }
-        );
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
     #[test]
-    fn addc0() {
-        let (v, c) = new(0).addc(new(1), false);
+    fn mulc32_0() {
+        let (v, c) = new(1).mulc32(1, new(0));
         assert_eq!(v, new(1));
-        assert_eq!(c, false);
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn addc1() {
-        let (v, c) = new(0).addc(new(1), true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn mulc32_1() {
+        let (v, c) = new(4).mulc32(10, new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn addc2() {
-        let (v, c) = new(0).max_value().addc(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn mulc32_2() {
+        let (v, c) = new(0).max_value().mulc32(1, new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
     #[test]
-    fn addc32_0() {
-        let (v, c) = new(0).addc32(1, false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
     }
 
     #[test]
-    fn addc32_1() {
-        let (v, c) = new(0).addc32(1, true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
     }
 
     #[test]
-    fn addc32_2() {
-        let (v, c) = new(0).max_value().addc32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
     }
 
     #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
     }
 
     #[test]
@@ -1906,89 +14881,43 @@ mod testszzzzzzzz {
         assert_eq!(c, true);
     }
 
-}
-
-impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
-    }
-
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
-    }
-
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
-    }
-
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
     }
+}
 
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
-    }
+#[cfg(test)]
+mod testszzzzzzzzzzz {
 
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
-    }
+    use crate::*;
 
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>>> {
+        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment().augment().augment().augment()
     }
 
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
     }
-}
-
-#[cfg(test)]
-mod testszzzzzzzzz {
-
-    use crate::*;
 
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>> {
-        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment().augment()
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
     }
 
     #[test]
@@ -2034,6 +14963,18 @@ mod testszzzzzzzzz {
         );
     }
 
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
     #[test]
     fn addc0() {
         let (v, c) = new(0).addc(new(1), false);
@@ -2077,276 +15018,478 @@ mod testszzzzzzzzz {
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
     }
 
     #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
     }
 
     #[test]
-    fn subb0() {
-        let (v, c) = new(1).subb(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
     }
 
     #[test]
-    fn subb1() {
-        let (v, c) = new(0).subb(new(1), false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
     }
 
     #[test]
-    fn subb2() {
-        let (v, c) = new(1).subb(new(1), true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
     }
 
     #[test]
-    fn subb32_0() {
-        let (v, c) = new(1).subb32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
     }
 
     #[test]
-    fn subb32_1() {
-        let (v, c) = new(0).subb32(1, false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
     }
 
     #[test]
-    fn subb32_2() {
-        let (v, c) = new(1).subb32(1, true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
     }
 
-}
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
+    }
 
-impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
     }
 
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
     }
 
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
     }
 
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
     }
 
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
+    }
+
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
+    }
+
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
         }
     }
 
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
     }
 
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
     }
 
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
     }
 
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
+    #[test]
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
     }
 
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
+    #[test]
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
     }
-}
 
-#[cfg(test)]
-mod testszzzzzzzzzz {
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
+    }
 
-    use crate::*;
+    #[test]
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
+    }
 
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>> {
-        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment().augment().augment()
+    #[test]
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
     }
 
     #[test]
-    fn eq0() {
-        assert_eq!(new(0), new(0));
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
+    }
+
+    #[test]
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
+    }
+
+    #[test]
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
+    }
+
+    #[test]
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
+    }
+
+    #[test]
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
+    }
+
+    #[test]
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
+    }
+
+    #[test]
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
+    }
+
+    #[test]
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
     }
 
     #[test]
-    fn eq1() {
-        assert_ne!(new(0), new(1));
+    fn mulc_0() {
+        let (v, c) = new(1).mulc(new(1), new(0));
+        assert_eq!(v, new(1));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn eq2() {
-        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    fn mulc_1() {
+        let (v, c) = new(4).mulc(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn ord0() {
-        assert!(new(0) < new(1));
+    fn mulc_2() {
+        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
     #[test]
-    fn ord1() {
-        assert!(new(0) < new(u32::max_value()));
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
     }
 
     #[test]
-    fn min_value0() {
-        assert_eq!(new(u32::max_value()).zero(), new(0));
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
     }
 
     #[test]
-    fn augment0() {
-        let v = new(u32::max_value());
-        let va = v.augment();
-        assert_eq!(
-            va,
-            Uz {
-                hi: v.zero(),
-                lo: v,
-            }
-        );
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
     }
 
     #[test]
-    fn addc0() {
-        let (v, c) = new(0).addc(new(1), false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
     }
 
     #[test]
-    fn addc1() {
-        let (v, c) = new(0).addc(new(1), true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
     }
 
     #[test]
-    fn addc2() {
-        let (v, c) = new(0).max_value().addc(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
     }
 
     #[test]
-    fn addc32_0() {
-        let (v, c) = new(0).addc32(1, false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn addc32_1() {
-        let (v, c) = new(0).addc32(1, true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
     }
 
     #[test]
-    fn addc32_2() {
-        let (v, c) = new(0).max_value().addc32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
         assert_eq!(c, new(0));
     }
 
     #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
         assert_eq!(v, new(122));
         assert_eq!(c, new(1));
     }
@@ -2372,6 +15515,89 @@ mod testszzzzzzzzzz {
         assert_eq!(c, new(1));
     }
 
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
     #[test]
     fn subb0() {
         let (v, c) = new(1).subb(new(1), false);
@@ -2414,89 +15640,43 @@ mod testszzzzzzzzzz {
         assert_eq!(c, true);
     }
 
-}
-
-impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
-    }
-
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
-    }
-
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
-    }
-
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
     }
+}
 
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
-    }
+#[cfg(test)]
+mod testszzzzzzzzzzzz {
 
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
-    }
+    use crate::*;
 
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
+    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>>>> {
+        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment().augment().augment().augment().augment()
     }
 
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
+    // Full-width operands a and b and the limbs of a * b + b, computed by
+    // schoolbook multiplication.
+    fn mul_reference() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let n = new(0).limbs() as u32;
+        let a: Vec<u32> = (0..n)
+            .map(|i| i.wrapping_mul(0x9e37_79b9) ^ 0x5555_5555)
+            .collect();
+        let b: Vec<u32> = (0..n).map(|i| !i.wrapping_mul(0x85eb_ca6b)).collect();
+        let mut want = limbs::mul(&a, &b);
+        limbs::add_assign(&mut want, &b);
+        (a, b, want)
     }
-}
-
-#[cfg(test)]
-mod testszzzzzzzzzzz {
-
-    use crate::*;
 
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>>> {
-        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment().augment().augment().augment()
+    fn limbs_of<T: Uintz>((lo, hi): (T, T)) -> Vec<u32> {
+        let n = lo.limbs();
+        let mut l = vec![0; 2 * n];
+        lo.write_limbs(&mut l[..n]);
+        hi.write_limbs(&mut l[n..]);
+        l
     }
 
     #[test]
@@ -2542,6 +15722,18 @@ mod testszzzzzzzzzzz {
         );
     }
 
+    #[test]
+    fn add_mod0() {
+        assert_eq!(new(5).add_mod(new(7), new(10)), new(2));
+        assert_eq!(new(25).add_mod(new(17), new(10)), new(2));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.add_mod(m1, m), m.subb32(2, false).0);
+        assert_eq!(m1.add_mod(new(2), m), new(1));
+        assert_eq!(m.add_mod(m, m), new(0));
+        assert_eq!(m.add_mod(m, m1), new(2));
+    }
+
     #[test]
     fn addc0() {
         let (v, c) = new(0).addc(new(1), false);
@@ -2585,257 +15777,364 @@ mod testszzzzzzzzzzz {
     }
 
     #[test]
-    fn mulc_0() {
-        let (v, c) = new(1).mulc(new(1), new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn bits0() {
+        assert_eq!(new(0).bits(), 0);
+        assert_eq!(new(1).bits(), 1);
+        assert_eq!(new(0x8000_0000).bits(), 32);
+        assert_eq!(new(0).max_value().bits(), new(0).limbs() as u32 * 32);
     }
 
     #[test]
-    fn mulc_1() {
-        let (v, c) = new(4).mulc(new(10), new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn checked_pow0() {
+        assert_eq!(new(3).checked_pow(4), Some(new(81)));
+        assert_eq!(new(0).checked_pow(0), Some(new(1)));
+        assert_eq!(new(0).max_value().checked_pow(1), Some(new(0).max_value()));
     }
 
     #[test]
-    fn mulc_2() {
-        let (v, c) = new(0).max_value().mulc(new(1), new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    fn checked_pow1() {
+        let bits = new(0).limbs() as u32 * 32;
+        let v = new(2).checked_pow(bits - 1).unwrap();
+        assert_eq!(v.mulc32(2, new(0)), (new(0), new(1)));
+        assert_eq!(new(2).checked_pow(bits), None);
+        assert_eq!(new(0).max_value().checked_pow(2), None);
     }
 
     #[test]
-    fn mulc32_0() {
-        let (v, c) = new(1).mulc32(1, new(0));
-        assert_eq!(v, new(1));
-        assert_eq!(c, new(0));
+    fn cldivr0() {
+        let m = new(0).max_value();
+        assert_eq!(m.cldivr(m.shr(1)), (new(2), new(1)));
+        assert_eq!(new(49).cldivr(new(7)), (new(11), new(0)));
+        assert_eq!(new(50).cldivr(new(7)), (new(11), new(3)));
+        assert_eq!(new(5).cldivr(new(9)), (new(0), new(5)));
+        assert_eq!(m.cldivr(new(1)), (m, new(0)));
     }
 
     #[test]
-    fn mulc32_1() {
-        let (v, c) = new(4).mulc32(10, new(3));
-        assert_eq!(v, new(43));
-        assert_eq!(c, new(0));
+    fn cldivr1() {
+        let b = new(0).max_value().shr(16);
+        let a = new(0x1a5);
+        let r = new(0x33);
+        assert_eq!(a.clmul(b).xor(r).cldivr(b), (a, r));
     }
 
     #[test]
-    fn mulc32_2() {
-        let (v, c) = new(0).max_value().mulc32(1, new(123));
-        assert_eq!(v, new(122));
-        assert_eq!(c, new(1));
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn cldivr2() {
+        new(1).cldivr(new(0));
     }
 
     #[test]
-    fn subb0() {
-        let (v, c) = new(1).subb(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn clmul0() {
+        assert_eq!(new(3).clmul(new(3)), new(5));
+        assert_eq!(new(0b1011).clmul(new(0b111)), new(0b110001));
+        let m = new(0).max_value();
+        assert_eq!(m.clmul(new(2)), m.shl(1));
+        let x = m.divr32(7).0;
+        let y = m.shr(3).subb32(12345, false).0;
+        assert_eq!(x.clmul(y), x.clmul_wide(y).0);
+        assert_eq!(x.clmul(y), y.clmul(x));
     }
 
     #[test]
-    fn subb1() {
-        let (v, c) = new(0).subb(new(1), false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn clmul_wide0() {
+        let m = new(0).max_value();
+        let p = m.divr32(3).0;
+        assert_eq!(m.clmul_wide(m), (p, p));
+        assert_eq!(m.clmul_wide(new(2)), (m.shl(1), new(1)));
+        assert_eq!(m.clmul_wide(new(3)), (new(1), new(1)));
+        assert_eq!(m.clmul_wide(new(0)), (new(0), new(0)));
     }
 
     #[test]
-    fn subb2() {
-        let (v, c) = new(1).subb(new(1), true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_addc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_addc(new(1), 0), (new(0), 1));
+        assert_eq!(m.ct_addc(m, 1), (m, 1));
+        assert_eq!(new(2).ct_addc(new(3), 1), (new(6), 0));
+        let x = m.shr(1);
+        assert_eq!(x.ct_addc(x, 1), (m, 0));
     }
 
     #[test]
-    fn subb32_0() {
-        let (v, c) = new(1).subb32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, false);
+    fn ct_conditional_swap0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_conditional_swap(m, 0), (new(1), m));
+        assert_eq!(new(1).ct_conditional_swap(m, 1), (m, new(1)));
     }
 
     #[test]
-    fn subb32_1() {
-        let (v, c) = new(0).subb32(1, false);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
+    fn ct_eq0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_eq(b), (a == b) as u32);
+            }
+        }
     }
 
     #[test]
-    fn subb32_2() {
-        let (v, c) = new(1).subb32(1, true);
-        assert_eq!(v, new(0).max_value());
-        assert_eq!(c, true);
-    }
-
-}
-
-impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
+    fn ct_lt0() {
+        let m = new(0).max_value();
+        let xs = [new(0), new(1), new(2), m, m.subb32(1, false).0, m.shr(1)];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                assert_eq!(a.ct_lt(b), (a < b) as u32);
+            }
+        }
     }
 
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
+    #[test]
+    fn ct_mulc0() {
+        let m = new(0).max_value();
+        assert_eq!(m.ct_mulc(m, m), m.mulc(m, m));
+        let x = m.shr(3).subb32(12345, false).0;
+        let y = m.shr(1).addc32(7, false).0;
+        assert_eq!(x.ct_mulc(y, new(5)), x.mulc(y, new(5)));
     }
 
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
+    #[test]
+    fn ct_select0() {
+        let m = new(0).max_value();
+        assert_eq!(new(1).ct_select(m, 0), new(1));
+        assert_eq!(new(1).ct_select(m, 1), m);
     }
 
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
+    #[test]
+    fn ct_subb0() {
+        let m = new(0).max_value();
+        assert_eq!(new(0).ct_subb(new(1), 0), (m, 1));
+        assert_eq!(new(0).ct_subb(new(0), 1), (m, 1));
+        assert_eq!(m.ct_subb(m, 1), (m, 1));
+        assert_eq!(new(7).ct_subb(new(3), 1), (new(3), 0));
     }
 
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
+    #[test]
+    fn divr0() {
+        assert_eq!(new(100).divr(new(7)), (new(14), new(2)));
+        assert_eq!(new(3).divr(new(5)), (new(0), new(3)));
+        let m = new(0).max_value();
+        assert_eq!(m.divr(m), (new(1), new(0)));
+        assert_eq!(m.divr(new(1)), (m, new(0)));
+        assert_eq!(m.divr(m.shr(1)), (new(2), new(1)));
     }
 
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
+    #[test]
+    fn divr1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let d = new(0).max_value().divr32(3).0.addc32(5, false).0;
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
+        let d = a.shr(d.bits() / 2 + 3);
+        let (q, r) = a.divr(d);
+        assert!(r < d);
+        assert_eq!(d.mulc(q, r), (a, new(0)));
     }
 
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
+    #[test]
+    #[should_panic]
+    fn divr2() {
+        new(1).divr(new(0));
     }
 
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
+    #[test]
+    fn divr32_0() {
+        let (v, r) = new(43).divr32(10);
+        assert_eq!(v, new(4));
+        assert_eq!(r, 3);
     }
 
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
+    #[test]
+    fn divr32c0() {
+        let (v, r) = new(0).max_value().divr32c(10, 9);
+        let (w, c) = v.mulc32(10, new(r));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(9));
+    }
+
+    #[test]
+    fn divr32_pre0() {
+        let x = new(0).max_value().subb32(12345, false).0;
+        for &d in &[1, 3, 10, 1_000_000_000, 0x8000_0001, u32::MAX] {
+            let dv = divisor::Divisor32::new(d);
+            assert_eq!(x.divr32_pre(&dv, 0), x.divr32(d));
+            assert_eq!(x.divr32_pre(&dv, d - 1), x.divr32c(d, d - 1));
+            assert_eq!(new(7).divr32_pre(&dv, 0), new(7).divr32(d));
         }
     }
-}
 
-#[cfg(test)]
-mod testszzzzzzzzzzzz {
+    #[test]
+    fn divr32_1() {
+        let (v, r) = new(0).max_value().divr32(u32::MAX);
+        let (w, c) = v.mulc32(u32::MAX, new(0));
+        assert_eq!(w, new(0).max_value());
+        assert_eq!(c, new(0));
+        assert_eq!(r, 0);
+    }
 
-    use crate::*;
+    #[test]
+    fn trailing_zeros0() {
+        assert_eq!(new(8).trailing_zeros(), 3);
+        assert_eq!(new(1).trailing_zeros(), 0);
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).trailing_zeros(), bits);
+        assert_eq!(new(0).max_value().shl(bits - 1).trailing_zeros(), bits - 1);
+    }
 
-    fn new(v: u32) -> Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>>>> {
-        from_u32(v).augment().augment().augment().augment().augment().augment().augment().augment().augment().augment().augment().augment()
+    #[test]
+    fn widening_square0() {
+        let (v, c) = new(12).widening_square();
+        assert_eq!(v, new(144));
+        assert_eq!(c, new(0));
     }
 
     #[test]
-    fn eq0() {
-        assert_eq!(new(0), new(0));
+    fn widening_square1() {
+        let m = new(0).max_value();
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
+        let m = m.subb32(0x1234_5678, false).0;
+        assert_eq!(m.widening_square(), m.mulc(m, new(0)));
     }
 
     #[test]
-    fn eq1() {
-        assert_ne!(new(0), new(1));
+    fn wrapping_pow0() {
+        assert_eq!(new(5).wrapping_pow(13), new(1_220_703_125));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).wrapping_pow(bits), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.wrapping_pow(3), m);
+        assert_eq!(m.wrapping_pow(4), new(1));
+    }
+
+    fn check_bezout<T: Uintz + PartialEq + std::fmt::Debug>(a: T, b: T, g: T) {
+        let (d, x, y, odd) = a.extended_gcd(b);
+        assert_eq!(d, g);
+        let (ax, by) = (a.mulc(x, a.zero()), b.mulc(y, a.zero()));
+        let (lo, hi) = if odd { (ax, by) } else { (by, ax) };
+        let (s, c) = lo.0.addc(g, false);
+        assert_eq!((s, lo.1.addc32(0, c).0), hi);
     }
 
     #[test]
-    fn eq2() {
-        assert_eq!(new(u32::max_value()), new(u32::max_value()));
+    fn extended_gcd0() {
+        check_bezout(new(240), new(46), new(2));
+        check_bezout(new(46), new(240), new(2));
+        check_bezout(new(17), new(5), new(1));
+        check_bezout(new(0), new(9), new(9));
+        check_bezout(new(9), new(0), new(9));
+        check_bezout(new(0), new(0), new(0));
+        let m = new(0).max_value();
+        check_bezout(m, new(3), new(3));
+        check_bezout(m, m.subb32(1, false).0, new(1));
+        check_bezout(m, new(0x1234_5678), new(3));
     }
 
     #[test]
-    fn ord0() {
-        assert!(new(0) < new(1));
+    fn gcd0() {
+        assert_eq!(new(12).gcd(new(18)), new(6));
+        assert_eq!(new(0).gcd(new(5)), new(5));
+        assert_eq!(new(5).gcd(new(0)), new(5));
+        assert_eq!(new(0).gcd(new(0)), new(0));
+        assert_eq!(new(17).gcd(new(31)), new(1));
+        assert_eq!(new(1 << 20).gcd(new(3 << 12)), new(1 << 12));
+        let m = new(0).max_value();
+        assert_eq!(m.gcd(m), m);
+        assert_eq!(m.gcd(new(3)), new(3));
+        assert_eq!(m.gcd(m.subb32(1, false).0), new(1));
+        assert_eq!(m.shl(1).gcd(new(6)), new(2));
     }
 
     #[test]
-    fn ord1() {
-        assert!(new(0) < new(u32::max_value()));
+    fn inv_mod0() {
+        assert_eq!(new(3).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(13).inv_mod(new(10)), Some(new(7)));
+        assert_eq!(new(12345).inv_mod(new(99991)), Some(new(82852)));
+        assert_eq!(new(2).inv_mod(new(4)), None);
+        assert_eq!(new(0).inv_mod(new(7)), None);
+        assert_eq!(new(5).inv_mod(new(1)), Some(new(0)));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.inv_mod(m), Some(m1));
+        assert_eq!(new(3).inv_mod(m), None);
+        let x = new(0x1234_5679).inv_mod(m1).unwrap();
+        assert_eq!(x.mul_mod(new(0x1234_5679), m1), new(1));
     }
 
     #[test]
-    fn min_value0() {
-        assert_eq!(new(u32::max_value()).zero(), new(0));
+    fn iroot0() {
+        assert_eq!(new(1000).iroot(3), new(10));
+        assert_eq!(new(999).iroot(3), new(9));
+        assert_eq!(new(2).iroot(5), new(1));
+        assert_eq!(new(0).iroot(3), new(0));
+        assert_eq!(new(1 << 30).iroot(2), new(1 << 15));
+        assert_eq!(new(u32::MAX).iroot(31), new(2));
+        assert_eq!(new(u32::MAX).iroot(32), new(1));
+        let m = new(0).max_value();
+        assert_eq!(m.iroot(1), m);
     }
 
     #[test]
-    fn augment0() {
-        let v = new(u32::max_value());
-        let va = v.augment();
-        assert_eq!(
-            va,
-            Uz {
-                hi: v.zero(),
-                lo: v,
-            }
-        );
+    #[should_panic]
+    fn iroot1() {
+        new(8).iroot(0);
     }
 
     #[test]
-    fn addc0() {
-        let (v, c) = new(0).addc(new(1), false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn is_perfect_power0() {
+        assert!(new(0).is_perfect_power());
+        assert!(new(1).is_perfect_power());
+        assert!(new(243).is_perfect_power());
+        assert!(new(1 << 31).is_perfect_power());
+        assert!(!new(12).is_perfect_power());
+        assert!(!new(u32::MAX).is_perfect_power());
     }
 
     #[test]
-    fn addc1() {
-        let (v, c) = new(0).addc(new(1), true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn is_perfect_square0() {
+        assert!(new(0).is_perfect_square());
+        assert!(new(144).is_perfect_square());
+        assert!(!new(145).is_perfect_square());
+        assert!(!new(0).max_value().is_perfect_square());
     }
 
     #[test]
-    fn addc2() {
-        let (v, c) = new(0).max_value().addc(new(1), false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn isqrt0() {
+        assert_eq!(new(0).isqrt(), new(0));
+        assert_eq!(new(1).isqrt(), new(1));
+        assert_eq!(new(15).isqrt(), new(3));
+        assert_eq!(new(16).isqrt(), new(4));
+        assert_eq!(new(u32::MAX).isqrt(), new(0xffff));
     }
 
     #[test]
-    fn addc32_0() {
-        let (v, c) = new(0).addc32(1, false);
-        assert_eq!(v, new(1));
-        assert_eq!(c, false);
+    fn isqrt_rem0() {
+        assert_eq!(new(17).isqrt_rem(), (new(4), new(1)));
+        let m = new(0).max_value();
+        let (s, r) = m.isqrt_rem();
+        assert_eq!(s, m.shr(m.bits() / 2));
+        assert_eq!(r, s.shl(1));
     }
 
     #[test]
-    fn addc32_1() {
-        let (v, c) = new(0).addc32(1, true);
-        assert_eq!(v, new(2));
-        assert_eq!(c, false);
+    fn lcm0() {
+        assert_eq!(new(4).lcm(new(6)), Some(new(12)));
+        assert_eq!(new(0).lcm(new(5)), Some(new(0)));
+        assert_eq!(new(7).lcm(new(7)), Some(new(7)));
+        let m = new(0).max_value();
+        assert_eq!(m.lcm(new(3)), Some(m));
+        assert_eq!(m.lcm(new(2)), None);
     }
 
     #[test]
-    fn addc32_2() {
-        let (v, c) = new(0).max_value().addc32(1, false);
-        assert_eq!(v, new(0));
-        assert_eq!(c, true);
+    fn limbs0() {
+        let v = new(0).max_value().subb32(1, false).0;
+        let mut l = vec![0; v.limbs()];
+        v.write_limbs(&mut l);
+        assert_eq!(l[0], u32::MAX - 1);
+        assert!(l[1..].iter().all(|&x| x == u32::MAX));
+        assert_eq!(v.zero().read_limbs(&l), v);
     }
 
     #[test]
@@ -2859,6 +16158,101 @@ mod testszzzzzzzzzzzz {
         assert_eq!(c, new(1));
     }
 
+    #[test]
+    fn lo32_0() {
+        assert_eq!(new(7).lo32(), 7);
+        assert_eq!(new(0).max_value().lo32(), u32::MAX);
+        assert_eq!(new(0).max_value().shl(4).lo32(), 0xffff_fff0);
+    }
+
+    #[test]
+    fn mul_hi0() {
+        assert_eq!(new(4).mul_hi(new(10)), new(0));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_hi(m), m.subb32(1, false).0);
+    }
+
+    #[test]
+    fn mul_hi1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_hi(b), a.mulc(b, new(0)).1);
+        assert_eq!(b.mul_hi(a), a.mulc(b, new(0)).1);
+    }
+
+    #[test]
+    fn mul_lo0() {
+        assert_eq!(new(4).mul_lo(new(10)), new(40));
+        let m = new(0).max_value();
+        assert_eq!(m.mul_lo(m), new(1));
+    }
+
+    #[test]
+    fn mul_lo1() {
+        let a = new(0).max_value().subb32(0x1234_5678, false).0;
+        let b = new(0).max_value().divr32(3).0;
+        assert_eq!(a.mul_lo(b), a.mulc(b, new(0)).0);
+        assert_eq!(b.mul_lo(a), a.mulc(b, new(0)).0);
+    }
+
+    #[test]
+    fn mul_mod0() {
+        assert_eq!(new(7).mul_mod(new(8), new(10)), new(6));
+        let m = new(0).max_value();
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.mul_mod(m1, m), new(1));
+        assert_eq!(m1.mul_mod(m.subb32(2, false).0, m), new(2));
+        assert_eq!(m.mul_mod(m, m1), new(1));
+        assert_eq!(m.mul_mod(new(3), new(1)), new(0));
+    }
+
+    #[test]
+    fn mul_ntt_0() {
+        let (v, c) = new(4).mul_ntt(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_ntt_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_ntt(y, y)), want);
+        let m = new(0).max_value();
+        assert_eq!(m.mul_ntt(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_ntt_2() {
+        let (v, c) = new(0).max_value().mul_ntt(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
+    #[test]
+    fn mul_toom3_0() {
+        let (v, c) = new(4).mul_toom3(new(10), new(3));
+        assert_eq!(v, new(43));
+        assert_eq!(c, new(0));
+    }
+
+    #[test]
+    fn mul_toom3_1() {
+        let (a, b, want) = mul_reference();
+        let (x, y) = (new(0).read_limbs(&a), new(0).read_limbs(&b));
+        assert_eq!(limbs_of(x.mul_toom3(y, y)), want);
+        // m * m + m = m * 2^w
+        let m = new(0).max_value();
+        assert_eq!(m.mul_toom3(m, m), (new(0), m));
+    }
+
+    #[test]
+    fn mul_toom3_2() {
+        let (v, c) = new(0).max_value().mul_toom3(new(1), new(123));
+        assert_eq!(v, new(122));
+        assert_eq!(c, new(1));
+    }
+
     #[test]
     fn mulc32_0() {
         let (v, c) = new(1).mulc32(1, new(0));
@@ -2880,6 +16274,89 @@ mod testszzzzzzzzzzzz {
         assert_eq!(c, new(1));
     }
 
+    #[test]
+    fn pow0() {
+        assert_eq!(new(10).pow(9), new(1_000_000_000));
+        assert_eq!(new(1).pow(0xffff), new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow1() {
+        new(0).max_value().pow(2);
+    }
+
+    #[test]
+    fn pow_mod0() {
+        assert_eq!(new(7).pow_mod(new(12345), new(99991)), new(34895));
+        assert_eq!(new(7).pow_mod(new(0), new(99991)), new(1));
+        assert_eq!(new(7).pow_mod(new(5), new(1)), new(0));
+        let m = new(0).max_value();
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).pow_mod(new(bits), m), new(1));
+        let m1 = m.subb32(1, false).0;
+        assert_eq!(m1.pow_mod(new(u32::MAX), m), m1);
+        assert_eq!(m.pow_mod(new(3), m1), new(1));
+    }
+
+    #[test]
+    fn pow_wide0() {
+        assert_eq!(new(3).pow_wide(new(4)), new(81));
+        let m = new(0).max_value();
+        assert_eq!(m.pow_wide(new(u32::MAX).addc32(2, false).0), m);
+        assert_eq!(m.pow_wide(new(6)), new(1));
+        assert_eq!(new(2).pow_wide(new(new(0).limbs() as u32 * 32)), new(0));
+    }
+
+    #[test]
+    fn saturating_pow0() {
+        assert_eq!(new(7).saturating_pow(3), new(343));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(2).saturating_pow(bits), new(0).max_value());
+    }
+
+    #[test]
+    fn shl0() {
+        assert_eq!(new(1).shl(5), new(32));
+        assert_eq!(new(3).shl(0), new(3));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shl(bits), new(0));
+        assert_eq!(new(1).shl(bits - 1).shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shl(bits - 1).bits(), bits);
+    }
+
+    #[test]
+    fn shr0() {
+        assert_eq!(new(6).shr(1), new(3));
+        assert_eq!(new(6).shr(0), new(6));
+        let bits = new(0).limbs() as u32 * 32;
+        assert_eq!(new(0).max_value().shr(bits - 1), new(1));
+        assert_eq!(new(0).max_value().shr(bits), new(0));
+        assert_eq!(new(0).max_value().shr(5).bits(), bits - 5);
+    }
+
+    #[test]
+    fn square0() {
+        assert_eq!(new(12).square(), new(144));
+        assert_eq!(new(0).max_value().square(), new(1));
+    }
+
+    #[test]
+    fn square1() {
+        let m = new(0).max_value().subb32(2, false).0;
+        assert_eq!(m.square(), m.mulc(m, new(0)).0);
+    }
+
+    #[test]
+    fn sub_mod0() {
+        assert_eq!(new(3).sub_mod(new(5), new(10)), new(8));
+        assert_eq!(new(5).sub_mod(new(3), new(10)), new(2));
+        assert_eq!(new(3).sub_mod(new(25), new(10)), new(8));
+        let m = new(0).max_value();
+        assert_eq!(new(0).sub_mod(new(1), m), m.subb32(1, false).0);
+        assert_eq!(new(1).sub_mod(m, m), new(1));
+    }
+
     #[test]
     fn subb0() {
         let (v, c) = new(1).subb(new(1), false);
@@ -2922,78 +16399,11 @@ mod testszzzzzzzzzzzz {
         assert_eq!(c, true);
     }
 
-}
-
-impl Uintz for Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz<Uz32>>>>>>>>>>>> {
-    fn addc(self, other: Self, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc(other.lo, carry);
-        let (hi, hic) = self.hi.addc(other.hi, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn addc32(self, other: u32, carry: bool) -> (Self, bool) {
-        let (lo, loc) = self.lo.addc32(other, carry);
-        let (hi, hic) = self.hi.addc32(0, loc);
-        (Self { hi, lo }, hic)
-    }
-
-    fn augment(self) -> Uz<Self> {
-        Uz {
-            hi: self.zero(),
-            lo: self,
-        }
-    }
-
-    fn divr32(self, divisor: u32) -> (Self, u32) {
-        (self, 0)
-    }
-
-    fn max_value(self) -> Self {
-        Self {
-            hi: self.hi.max_value(),
-            lo: self.hi.max_value(),
-        }
-    }
-
-    fn mulc(self, other: Self, carry: Self) -> (Self, Self) {
-        let (ll, llc) = self.lo.mulc(other.lo, carry.lo);
-        let (hl, hlc) = self.hi.mulc(other.lo, llc);
-        let (lh, lhc) = self.lo.mulc(other.hi, hl);
-        let (hh, hhc) = self.hi.mulc(other.hi, hlc);
-        let (ah, ahc) = lh.addc(carry.hi, false);
-        let (ac, acc) = lhc.addc(hh, ahc);
-        let (aa, _) = hhc.addc32(0, acc);
-        (Self { hi: ah, lo: ll }, Self { hi: aa, lo: ac })
-    }
-
-    fn mulc32(self, other: u32, carry: Self) -> (Self, Self) {
-        let (lo, loc) = self.lo.mulc32(other, carry.lo);
-        let (hi, hic) = self.hi.mulc32(other, loc);
-        (
-            Self { hi, lo },
-            Self {
-                hi: hic.zero(),
-                lo: hic,
-            },
-        )
-    }
-
-    fn subb(self, other: Self, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb(other.lo, borrow);
-        let (hi, hib) = self.hi.subb(other.hi, lob);
-        (Self { lo, hi }, hib)
-    }
-
-    fn subb32(self, other: u32, borrow: bool) -> (Self, bool) {
-        let (lo, lob) = self.lo.subb32(other, borrow);
-        let (hi, hib) = self.hi.subb32(0, lob);
-        (Self { lo, hi }, hib)
-    }
-
-    fn zero(self) -> Self {
-        Self {
-            hi: self.hi.zero(),
-            lo: self.hi.zero(),
-        }
+    #[test]
+    fn xor0() {
+        let m = new(0).max_value();
+        assert_eq!(m.xor(m), new(0));
+        assert_eq!(new(5).xor(new(3)), new(6));
+        assert_eq!(m.xor(new(1)), m.subb32(1, false).0);
     }
 }